@@ -0,0 +1,73 @@
+namespace Sokoban;
+
+public static class Deadlock {
+
+    // Returns if the box on the given cell is frozen together with at least one frozen box that is not on a target.
+    // Boxes are frozen when they can be moved along neither axis: blocked by walls, by dead squares on both sides
+    // or by other frozen boxes (2x2 blocks, boxes paired against a wall, etc).
+    public static bool IsFreezeDeadlock(Level level, State state, int box) {
+
+        List<int> frozenBoxes = new List<int>();
+
+        if (!IsFrozen(level, state, box, frozenBoxes)) {
+            return false;
+        }
+
+        // Frozen boxes that are all on targets are a valid final position
+        foreach (var frozenBox in frozenBoxes) {
+            if (!level.IsTarget(frozenBox)) {
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    // Returns if the box can't be moved along any axis.
+    // Boxes being checked are treated as walls to avoid cycles and are released again if they turn out not to be frozen.
+    private static bool IsFrozen(Level level, State state, int box, List<int> frozenBoxes) {
+
+        int marked = frozenBoxes.Count;
+        frozenBoxes.Add(box);
+
+        // Horizontal (right) and vertical (down) axes
+        foreach (var axis in new int[] { level.Directions[1], level.Directions[2], }) {
+            if (!IsBlocked(level, state, box, axis, frozenBoxes)) {
+                frozenBoxes.RemoveRange(marked, frozenBoxes.Count - marked);
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    // Returns if the box can't be moved along the given axis
+    private static bool IsBlocked(Level level, State state, int box, int axis, List<int> frozenBoxes) {
+
+        int before = box - axis;
+        int after = box + axis;
+
+        // Wall on either side
+        if (level.IsWall(before) || level.IsWall(after)) {
+            return true;
+        }
+
+        // Dead squares on both sides
+        if (level.IsDeadSquare(before) && level.IsDeadSquare(after)) {
+            return true;
+        }
+
+        // Frozen box on either side
+        foreach (var neighbour in new int[] { before, after, }) {
+            if (frozenBoxes.Contains(neighbour)) {
+                return true;
+            }
+
+            if (state.HasBox(neighbour) && IsFrozen(level, state, neighbour, frozenBoxes)) {
+                return true;
+            }
+        }
+
+        return false;
+    }
+}
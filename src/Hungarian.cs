@@ -0,0 +1,80 @@
+namespace Sokoban;
+
+public static class Hungarian {
+
+    // Returns the minimum total cost of assigning every row to a different column (rows <= columns).
+    // Hungarian algorithm with potentials, O(rows^2 * columns).
+    public static int MinimumCost(int[,] costs) {
+
+        int n = costs.GetLength(0);
+        int m = costs.GetLength(1);
+
+        // Potentials and matching are 1-indexed, column 0 is a dummy used to start every augmenting path
+        int[] u = new int[n + 1];
+        int[] v = new int[m + 1];
+        int[] match = new int[m + 1];   // Row matched to each column
+        int[] way = new int[m + 1];     // Previous column in the augmenting path
+
+        for (int i = 1; i <= n; i++) {
+            int[] minValues = new int[m + 1];
+            bool[] used = new bool[m + 1];
+            int column = 0;
+
+            Array.Fill(minValues, int.MaxValue);
+            match[0] = i;
+
+            // Grow the augmenting path until a free column is reached
+            do {
+                used[column] = true;
+                int row = match[column];
+                int delta = int.MaxValue;
+                int nextColumn = 0;
+
+                for (int j = 1; j <= m; j++) {
+                    if (!used[j]) {
+                        int reduced = costs[row - 1, j - 1] - u[row] - v[j];
+
+                        if (reduced < minValues[j]) {
+                            minValues[j] = reduced;
+                            way[j] = column;
+                        }
+
+                        if (minValues[j] < delta) {
+                            delta = minValues[j];
+                            nextColumn = j;
+                        }
+                    }
+                }
+
+                for (int j = 0; j <= m; j++) {
+                    if (used[j]) {
+                        u[match[j]] += delta;
+                        v[j] -= delta;
+                    }
+                    else {
+                        minValues[j] -= delta;
+                    }
+                }
+
+                column = nextColumn;
+            } while (match[column] != 0);
+
+            // Flip the matching along the augmenting path
+            do {
+                int previousColumn = way[column];
+                match[column] = match[previousColumn];
+                column = previousColumn;
+            } while (column != 0);
+        }
+
+        int total = 0;
+
+        for (int j = 1; j <= m; j++) {
+            if (match[j] != 0) {
+                total += costs[match[j] - 1, j - 1];
+            }
+        }
+
+        return total;
+    }
+}
@@ -2,24 +2,181 @@ namespace Sokoban;
 
 public class Level {
 
+    private const int ZOBRIST_SEED = 20241;
+
+    public const int INFINITE_DISTANCE = 1000000;   // Push distance of cells that can't reach a target
+
     private int rows;
     private int cols;
+    private int stride;             // Cells per row, one extra wall column so moves never wrap around rows
     private int[][] walls;
     private int[][] targets;
+    private ulong[] wallsBits;
+    private ulong[] targetsBits;
+    private ulong[] deadSquaresBits;
+    private ulong[] horizontalTunnelBits;   // Floor cells with walls above and below
+    private ulong[] verticalTunnelBits;     // Floor cells with walls on the left and on the right
+    private int[][] pushDistances;  // Pushes needed to move a lone box from each cell to each target
+    private int[] nearestDistances; // Pushes needed to move a lone box from each cell to its nearest target
+    private int[] directions;       // Cell offsets of u/r/d/l moves (clock-wise)
+    private ulong[] zobristBoxes;
+    private ulong[] zobristPlayer;
 
     // Level constructor
     public Level(string level) {
         rows = RowsCount(level);
         cols = ColsCount(level);
+        stride = cols + 1;
+
+        if (rows * stride > short.MaxValue) {
+            throw new InvalidOperationException("The level is too big.");
+        }
+
         walls = ObtainWallsCoords(level);        // Initialize walls coords
         targets = ObtainTargetsCoords(level);    // Initialize targets coords
+        directions = new int[] { -stride, 1, stride, -1, };
+        wallsBits = ObtainWallsBits();           // Initialize walls bitset
+        targetsBits = ObtainCoordsBits(targets); // Initialize targets bitset
+        pushDistances = ObtainPushDistances();   // Initialize push distances per target
+        nearestDistances = ObtainNearestDistances(); // Initialize push distances to the nearest target
+        deadSquaresBits = ObtainDeadSquares();   // Initialize dead squares bitset
+        horizontalTunnelBits = ObtainTunnels(directions[0]); // Initialize tunnels pushed along rows
+        verticalTunnelBits = ObtainTunnels(directions[1]);   // Initialize tunnels pushed along columns
+
+        // Initialize Zobrist keys (fixed seed so hashes are reproducible between runs)
+        Random random = new Random(ZOBRIST_SEED);
+        zobristBoxes = ObtainZobristKeys(random);
+        zobristPlayer = ObtainZobristKeys(random);
     }
 
     // Properties
     public int Rows => rows;                    // Getter rows
     public int Cols => cols;                    // Getter cols
+    public int CellsCount => rows * stride;     // Getter number of cells (including the extra column)
     public int[][] Walls => walls;              // Getter walls
     public int[][] Targets => targets;          // Getter targets
+    public int[] Directions => directions;      // Getter u/r/d/l cell offsets
+
+    // Returns the flat cell index of a coordinate
+    public int Cell(int row, int col) {
+        return row * stride + col;
+    }
+
+    // Returns the row of a cell
+    public int Row(int cell) {
+        return cell / stride;
+    }
+
+    // Returns the column of a cell
+    public int Col(int cell) {
+        return cell % stride;
+    }
+
+    // Returns the cell offset of a move (u/r/d/l or U/R/D/L)
+    public int Direction(char move) {
+        switch (char.ToLower(move)) {
+            case 'u':
+                return directions[0];
+            case 'r':
+                return directions[1];
+            case 'd':
+                return directions[2];
+            case 'l':
+                return directions[3];
+            default:
+                throw new ArgumentException("Invalid move: " + move);
+        }
+    }
+
+    // Returns if the cell is a wall, cells outside the level count as walls
+    public bool IsWall(int cell) {
+        return cell < 0 || cell >= CellsCount || GetBit(wallsBits, cell);
+    }
+
+    // Returns if the cell is a target
+    public bool IsTarget(int cell) {
+        return cell >= 0 && cell < CellsCount && GetBit(targetsBits, cell);
+    }
+
+    // Returns if a box placed on the cell can never reach any target
+    public bool IsDeadSquare(int cell) {
+        return cell >= 0 && cell < CellsCount && GetBit(deadSquaresBits, cell);
+    }
+
+    // Returns if the cell is part of a one-wide tunnel along the push direction (walls on both sides of the push axis)
+    public bool IsTunnel(int cell, int direction) {
+        if (cell < 0 || cell >= CellsCount) {
+            return false;
+        }
+        return GetBit(Math.Abs(direction) == 1 ? horizontalTunnelBits : verticalTunnelBits, cell);
+    }
+
+    // Returns if a box placed on the coordinate can never reach any target
+    public bool IsDeadSquare(int row, int col) {
+        if (row < 0 || row >= rows || col < 0 || col >= cols) {
+            return false;
+        }
+        return IsDeadSquare(Cell(row, col));
+    }
+
+    // Returns the minimum number of pushes to move a lone box from the cell to a target (by target index)
+    public int PushDistance(int cell, int target) {
+        return pushDistances[target][cell];
+    }
+
+    // Returns the minimum number of pushes to move a lone box from the cell to its nearest target
+    public int NearestPushDistance(int cell) {
+        return nearestDistances[cell];
+    }
+
+    // Returns the Zobrist key of a box on the cell
+    public ulong ZobristBox(int cell) {
+        return zobristBoxes[cell];
+    }
+
+    // Returns the Zobrist key of the player on the cell
+    public ulong ZobristPlayer(int cell) {
+        return zobristPlayer[cell];
+    }
+
+    // Returns the Zobrist hash of a set of boxes, independent of their order
+    public ulong ZobristBoxesHash(short[] boxes) {
+
+        ulong hash = 0;
+
+        foreach (var box in boxes) {
+            hash ^= zobristBoxes[box];
+        }
+
+        return hash;
+    }
+
+    // Returns the cells reachable by the player (flood fill), each one mapped to the cell it was reached from.
+    // Unreachable cells are set to -1 and the player cell to itself.
+    public int[] ReachableRegion(State state) {
+
+        int[] parents = new int[CellsCount];
+        Queue<int> queue = new Queue<int>();
+
+        Array.Fill(parents, -1);
+        parents[state.Player] = state.Player;
+        queue.Enqueue(state.Player);
+
+        while (queue.Count > 0) {
+            int cell = queue.Dequeue();
+
+            foreach (var direction in directions) {
+                int next = cell + direction;
+
+                if (!IsWall(next) && !state.HasBox(next) && parents[next] == -1) {
+                    parents[next] = cell;
+                    queue.Enqueue(next);
+                }
+            }
+        }
+
+        return parents;
+    }
 
     // Counts the number of rows
     private int RowsCount(string level) {
@@ -42,6 +199,11 @@ public class Level {
                 count++;
             }
         }
+
+        // Last row may not end with a newline
+        if (count > maxCols) {
+            maxCols = count;
+        }
         return maxCols;
     }
 
@@ -94,4 +256,262 @@ public class Level {
         return targets;
     }
 
+    // Returns one random 64-bit key per cell
+    private ulong[] ObtainZobristKeys(Random random) {
+
+        ulong[] keys = new ulong[CellsCount];
+        byte[] buffer = new byte[8];
+
+        for (int i = 0; i < keys.Length; i++) {
+            random.NextBytes(buffer);
+            keys[i] = BitConverter.ToUInt64(buffer, 0);
+        }
+
+        return keys;
+    }
+
+    // Returns the walls bitset, the extra column at the end of every row is a wall too
+    private ulong[] ObtainWallsBits() {
+
+        ulong[] bits = ObtainCoordsBits(walls);
+
+        for (int i = 0; i < rows; i++) {
+            SetBit(bits, Cell(i, cols));
+        }
+
+        return bits;
+    }
+
+    // Returns a bitset with the cells of a coordinates array
+    private ulong[] ObtainCoordsBits(int[][] coords) {
+
+        ulong[] bits = new ulong[(CellsCount + 63) / 64];
+
+        foreach (var coord in coords) {
+            SetBit(bits, Cell(coord[0], coord[1]));
+        }
+
+        return bits;
+    }
+
+    // Returns the dead squares bitset: floor cells from which no box can ever be pushed onto a target
+    private ulong[] ObtainDeadSquares() {
+
+        ulong[] dead = new ulong[wallsBits.Length];
+
+        for (int cell = 0; cell < CellsCount; cell++) {
+            if (!IsWall(cell) && nearestDistances[cell] == INFINITE_DISTANCE) {
+                SetBit(dead, cell);
+            }
+        }
+
+        return dead;
+    }
+
+    // Returns the tunnels bitset: floor cells with walls on both sides along the given offset
+    private ulong[] ObtainTunnels(int side) {
+
+        ulong[] tunnels = new ulong[wallsBits.Length];
+
+        for (int cell = 0; cell < CellsCount; cell++) {
+            if (!IsWall(cell) && IsWall(cell - side) && IsWall(cell + side)) {
+                SetBit(tunnels, cell);
+            }
+        }
+
+        return tunnels;
+    }
+
+    // Returns, for every target, the pushes needed to bring a lone box there from each cell.
+    // Boxes are pulled backwards from the target (box cell + side of the player). Between pulls the player
+    // can only walk to the sides of the box connected to its own side without crossing the box.
+    private int[][] ObtainPushDistances() {
+
+        int[][] distances = new int[targets.Length][];
+        bool[][] connectedSides = ObtainConnectedSides();
+
+        for (int t = 0; t < targets.Length; t++) {
+            int[] distance = new int[CellsCount];
+            int[] sideDistance = new int[CellsCount * 4];
+            Queue<int> queue = new Queue<int>();
+            int target = Cell(targets[t][0], targets[t][1]);
+
+            Array.Fill(distance, INFINITE_DISTANCE);
+            Array.Fill(sideDistance, INFINITE_DISTANCE);
+
+            // The last push can leave the player on any free side of the target
+            for (int side = 0; side < 4; side++) {
+                if (!IsWall(target + directions[side])) {
+                    VisitSide(target, side, 0, sideDistance, connectedSides, queue);
+                }
+            }
+
+            while (queue.Count > 0) {
+                int boxSide = queue.Dequeue();
+                int box = boxSide / 4;
+                int side = boxSide % 4;
+
+                // Pull: the box moves to the player cell and the player steps back one more
+                int newBox = box + directions[side];
+                int player = newBox + directions[side];
+
+                if (!IsWall(player) && sideDistance[newBox * 4 + side] == INFINITE_DISTANCE) {
+                    VisitSide(newBox, side, sideDistance[boxSide] + 1, sideDistance, connectedSides, queue);
+                }
+            }
+
+            for (int cell = 0; cell < CellsCount; cell++) {
+                for (int side = 0; side < 4; side++) {
+                    distance[cell] = Math.Min(distance[cell], sideDistance[cell * 4 + side]);
+                }
+            }
+
+            distances[t] = distance;
+        }
+
+        return distances;
+    }
+
+    // Sets the distance of a box side and of every side the player can walk to from it, then queues them
+    private void VisitSide(int box, int side, int distance, int[] sideDistance, bool[][] connectedSides, Queue<int> queue) {
+        for (int other = 0; other < 4; other++) {
+            if (connectedSides[box][side * 4 + other] && sideDistance[box * 4 + other] == INFINITE_DISTANCE) {
+                sideDistance[box * 4 + other] = distance;
+                queue.Enqueue(box * 4 + other);
+            }
+        }
+    }
+
+    // Returns, for every floor cell holding a box, which pairs of its free sides (side * 4 + other)
+    // the player can walk between without crossing the box. Two sides are connected when the moves
+    // from the box to both of them belong to the same biconnected component of the floor.
+    private bool[][] ObtainConnectedSides() {
+
+        bool[][] connected = new bool[CellsCount][];
+        int[] components = ObtainMoveComponents();
+
+        for (int box = 0; box < CellsCount; box++) {
+            connected[box] = new bool[16];
+
+            if (IsWall(box)) {
+                continue;
+            }
+
+            for (int side = 0; side < 4; side++) {
+                for (int other = 0; other < 4; other++) {
+                    connected[box][side * 4 + other] = !IsWall(box + directions[side]) && !IsWall(box + directions[other])
+                        && components[box * 4 + side] == components[box * 4 + other];
+                }
+            }
+        }
+
+        return connected;
+    }
+
+    // Returns the biconnected component of every move between two floor cells (cell * 4 + side, -1 into walls).
+    // Iterative Tarjan depth-first search over the floor, a single pass for the whole level.
+    private int[] ObtainMoveComponents() {
+
+        int[] components = new int[CellsCount * 4];
+        int[] order = new int[CellsCount];      // Discovery order of every cell (-1 if not visited)
+        int[] low = new int[CellsCount];        // Lowest discovery order reachable through one back move
+        int[] nextSide = new int[CellsCount];   // Next side to explore from every cell
+        int[] parents = new int[CellsCount];
+        int[] parentSides = new int[CellsCount];
+        Stack<int> path = new Stack<int>();
+        Stack<int> moves = new Stack<int>();
+        int time = 0;
+        int componentsCount = 0;
+
+        Array.Fill(components, -1);
+        Array.Fill(order, -1);
+
+        for (int root = 0; root < CellsCount; root++) {
+            if (IsWall(root) || order[root] != -1) {
+                continue;
+            }
+
+            order[root] = low[root] = time++;
+            parents[root] = -1;
+            path.Push(root);
+
+            while (path.Count > 0) {
+                int cell = path.Peek();
+
+                if (nextSide[cell] < 4) {
+                    int side = nextSide[cell]++;
+                    int next = cell + directions[side];
+
+                    if (IsWall(next)) {
+                        continue;
+                    }
+
+                    if (order[next] == -1) {
+                        moves.Push(cell * 4 + side);
+                        parents[next] = cell;
+                        parentSides[next] = side;
+                        order[next] = low[next] = time++;
+                        path.Push(next);
+                    }
+                    else if (next != parents[cell] && order[next] < order[cell]) {
+                        moves.Push(cell * 4 + side);
+                        low[cell] = Math.Min(low[cell], order[next]);
+                    }
+                    continue;
+                }
+
+                path.Pop();
+                int parent = parents[cell];
+
+                if (parent == -1) {
+                    continue;
+                }
+
+                low[parent] = Math.Min(low[parent], low[cell]);
+
+                // The parent separates the subtree of the cell: its moves form a component
+                if (low[cell] >= order[parent]) {
+                    int move;
+
+                    do {
+                        move = moves.Pop();
+                        int from = move / 4;
+                        int side = move % 4;
+                        components[move] = componentsCount;
+                        components[(from + directions[side]) * 4 + (side + 2) % 4] = componentsCount;
+                    } while (move != parent * 4 + parentSides[cell]);
+
+                    componentsCount++;
+                }
+            }
+        }
+
+        return components;
+    }
+
+    // Returns the push distance of every cell to its nearest target
+    private int[] ObtainNearestDistances() {
+
+        int[] nearest = new int[CellsCount];
+
+        for (int cell = 0; cell < CellsCount; cell++) {
+            nearest[cell] = INFINITE_DISTANCE;
+
+            foreach (var distance in pushDistances) {
+                nearest[cell] = Math.Min(nearest[cell], distance[cell]);
+            }
+        }
+
+        return nearest;
+    }
+
+    // Returns if the bit of a cell is set
+    private static bool GetBit(ulong[] bits, int cell) {
+        return (bits[cell >> 6] & (1UL << (cell & 63))) != 0;
+    }
+
+    // Sets the bit of a cell
+    private static void SetBit(ulong[] bits, int cell) {
+        bits[cell >> 6] |= 1UL << (cell & 63);
+    }
 }
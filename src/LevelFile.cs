@@ -0,0 +1,175 @@
+namespace Sokoban;
+
+public class LevelFile {
+
+    private string title;
+    private string author;
+    private string comment;
+    private string solution;
+    private string level;
+
+    // LevelFile constructor (one level of a collection file)
+    public LevelFile(string level, string title) {
+        this.level = level;
+        this.title = title;
+        author = "";
+        comment = "";
+        solution = "";
+    }
+
+    // Properties
+    public string Level => level;               // Level Getter (rows separated by '\n')
+
+    // Title Getter and Setter
+    public string Title {
+        get => title;
+        set => title = value;
+    }
+
+    // Author Getter and Setter
+    public string Author {
+        get => author;
+        set => author = value;
+    }
+
+    // Comment Getter and Setter
+    public string Comment {
+        get => comment;
+        set => comment = value;
+    }
+
+    // Solution Getter and Setter (LURD, empty if the level is not solved)
+    public string Solution {
+        get => solution;
+        set => solution = value;
+    }
+
+    // Returns every level of a XSB / SOK / TXT collection file
+    public static List<LevelFile> Load(string path) {
+        return Parse(File.ReadAllLines(path));
+    }
+
+    // Returns the levels of a collection: boards are runs of board rows, separated by blank or text lines.
+    // "Title:", "Author:" and "Comment:" lines and ';' comments after a board belong to it, a plain
+    // text line before a board is used as its title when there is no "Title:" line.
+    public static List<LevelFile> Parse(IEnumerable<string> lines) {
+
+        List<LevelFile> levels = new List<LevelFile>();
+        List<string> boardRows = new List<string>();
+        LevelFile? current = null;
+        string pendingTitle = "";
+
+        foreach (var rawLine in lines) {
+            string line = rawLine.TrimEnd('\r');
+
+            if (IsBoardRow(line)) {
+                boardRows.Add(line.Replace('-', ' ').Replace('_', ' '));
+                continue;
+            }
+
+            // First line after a board closes it
+            if (boardRows.Count > 0) {
+                current = new LevelFile(string.Join("\n", boardRows), pendingTitle);
+                levels.Add(current);
+                boardRows.Clear();
+                pendingTitle = "";
+            }
+
+            string text = line.Trim();
+
+            if (text.Length == 0) {
+                continue;
+            }
+
+            if (text.StartsWith(';')) {
+                if (current != null) {
+                    current.AppendComment(text.Substring(1).Trim());
+                }
+            }
+            else if (TryMetadata(text, "Title", out string value)) {
+                if (current != null) {
+                    current.Title = value;
+                }
+                else {
+                    pendingTitle = value;
+                }
+            }
+            else if (TryMetadata(text, "Author", out value)) {
+                if (current != null) {
+                    current.Author = value;
+                }
+            }
+            else if (TryMetadata(text, "Comment", out value)) {
+                if (current != null) {
+                    current.AppendComment(value);
+                }
+            }
+            else {
+                pendingTitle = text;
+            }
+        }
+
+        if (boardRows.Count > 0) {
+            levels.Add(new LevelFile(string.Join("\n", boardRows), pendingTitle));
+        }
+
+        return levels;
+    }
+
+    // Returns the level selected by its 1-based index or by its title (case insensitive)
+    public static LevelFile Select(List<LevelFile> levels, string selector) {
+
+        if (levels.Count == 0) {
+            throw new InvalidOperationException("The file doesn't contain any level.");
+        }
+
+        if (int.TryParse(selector, out int index)) {
+            if (index < 1 || index > levels.Count) {
+                throw new ArgumentException("Level index out of range (1-" + levels.Count + "): " + index);
+            }
+            return levels[index - 1];
+        }
+
+        foreach (var level in levels) {
+            if (string.Equals(level.Title, selector, StringComparison.OrdinalIgnoreCase)) {
+                return level;
+            }
+        }
+
+        throw new ArgumentException("Level not found: " + selector);
+    }
+
+    // Adds a line to the level comment
+    private void AppendComment(string text) {
+        comment = comment.Length == 0 ? text : comment + "\n" + text;
+    }
+
+    // Returns if the line is a board row: only board characters ('-' and '_' are floors) and at least one wall
+    private static bool IsBoardRow(string line) {
+
+        if (!line.Contains('#')) {
+            return false;
+        }
+
+        foreach (char c in line) {
+            if ("#@+$*. -_".IndexOf(c) < 0) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    // Returns if the line is a "Key: value" metadata line for the given key
+    private static bool TryMetadata(string line, string key, out string value) {
+
+        value = "";
+
+        if (!line.StartsWith(key + ":", StringComparison.OrdinalIgnoreCase)) {
+            return false;
+        }
+
+        value = line.Substring(key.Length + 1).Trim();
+        return true;
+    }
+}
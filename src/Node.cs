@@ -2,17 +2,20 @@ namespace Sokoban;
 
 public class Node {
 
+    // Scale of the primary metric in lexicographic objectives (secondary metrics stay below it)
+    public const int LEXICOGRAPHIC_WEIGHT = 100000;
+
     private int idNode;
     private State stateNode;
     private Node? parentNode;
     private string action;
     private int depth;
-    private float cost;
-    private float heuristic;
-    private float valueNode;
+    private double cost;
+    private double heuristic;
+    private double valueNode;
 
     // Node constructor
-    public Node(int idNode, State stateNode, Node? parentNode, string action, int depth, float cost, float heuristic, float valueNode) {
+    public Node(int idNode, State stateNode, Node? parentNode, string action, int depth, double cost, double heuristic, double valueNode) {
         this.idNode = idNode;
         this.stateNode = stateNode;
         this.parentNode = parentNode;
@@ -54,26 +57,29 @@ public class Node {
     }
 
     // Cost Getter and Setter
-    public float Cost {
+    public double Cost {
         get => cost;
         set => cost = value;
     }
 
     // Heuristic Getter and Setter
-    public float Heuristic {
+    public double Heuristic {
         get => heuristic;
         set => heuristic = value;
     }
 
     // ValueNode Getter and Setter
-    public float ValueNode {
+    public double ValueNode {
         get => valueNode;
         set => valueNode = value;
     }
 
-    // Assign value to the value depending on the entered strategy
-    public void AssignValue(string strategy, Level level) {
-        switch (strategy) {
+    // Assign value to the value depending on the entered strategy, heuristic, weight and objective
+    public void AssignValue(Param param, Level level) {
+        string heuristicName = param.Heuristic;
+        string objective = param.Objective;
+
+        switch (param.Strategy) {
             case "BFS":
                 valueNode = depth;
                 break;
@@ -84,19 +90,92 @@ public class Node {
                 valueNode = cost;
                 break;
             case "GREEDY":
-                heuristic = HeuristicManhattan(level, stateNode);
+                heuristic = CalculateHeuristic(heuristicName, level, objective);
                 valueNode = heuristic;
                 break;
             case "A*":
-                heuristic = HeuristicManhattan(level, stateNode);
+                heuristic = CalculateHeuristic(heuristicName, level, objective);
+                valueNode = cost + heuristic;
+                break;
+            case "WA*":
+                heuristic = CalculateHeuristic(heuristicName, level, objective);
+                valueNode = cost + param.Weight * heuristic;
+                break;
+            case "IDDFS":
+                valueNode = depth;
+                break;
+            case "IDA*":
+                heuristic = CalculateHeuristic(heuristicName, level, objective);
                 valueNode = cost + heuristic;
                 break;
         }
     }
 
+    // Calculate the heuristic of the node state depending on the entered heuristic name.
+    // Every heuristic is a lower bound on the remaining pushes, and so on the remaining moves,
+    // which gives a lower bound on the cost of any objective.
+    private double CalculateHeuristic(string heuristicName, Level level, string objective) {
+        int pushes;
+
+        switch (heuristicName) {
+            case "MATCHING":
+                pushes = HeuristicMatching(level, stateNode);
+                break;
+            case "PUSH":
+                pushes = HeuristicPushDistance(level, stateNode);
+                break;
+            default:
+                pushes = HeuristicManhattan(level, stateNode);
+                break;
+        }
+
+        return ObjectiveCost(objective, pushes, pushes);
+    }
+
+    // Calculate the cost of a number of moves and pushes for the entered objective
+    public static double ObjectiveCost(string objective, int moves, int pushes) {
+        switch (objective) {
+            case "PUSHES":
+                return pushes;
+            case "MOVES_PUSHES":
+                return (double)moves * LEXICOGRAPHIC_WEIGHT + pushes;
+            case "PUSHES_MOVES":
+                return (double)pushes * LEXICOGRAPHIC_WEIGHT + moves;
+            default:
+                return moves;
+        }
+    }
+
+    // Calculate the sum of the pushes needed to move each box to its nearest target (walls are taken into account)
+    public int HeuristicPushDistance(Level level, State state) {
+        int total = 0;
+
+        foreach (var box in state.Boxes) {
+            total += level.NearestPushDistance(box);
+        }
+
+        return total;
+    }
+
+    // Calculate the minimum total pushes of a matching between boxes and targets (Hungarian algorithm).
+    // Unlike the Manhattan heuristic, every box is assigned a different target and walls are taken into account.
+    // With more targets than boxes, the surplus targets are simply left unmatched.
+    public int HeuristicMatching(Level level, State state) {
+        short[] boxes = state.Boxes;
+        int[,] distances = new int[boxes.Length, level.Targets.Length];
+
+        for (int i = 0; i < boxes.Length; i++) {
+            for (int j = 0; j < level.Targets.Length; j++) {
+                distances[i, j] = level.PushDistance(boxes[i], j);
+            }
+        }
+
+        return Hungarian.MinimumCost(distances);
+    }
+
     // Calculate the Manhattan distance between a box and a target
     public int HeuristicManhattan(Level level, State state) {
-        int[][] boxes = state.Boxes;
+        short[] boxes = state.Boxes;
         int[][] targets = level.Targets;
         int[][] dMBoxesTargets = new int[boxes.Length][];
         int[] minDistances = new int[boxes.Length];
@@ -115,8 +194,8 @@ public class Node {
             for (int j = 0; j < targets.Length; j++) {
                 int Trow = targets[j][0];
                 int Tcolumn = targets[j][1];
-                int Brow = boxes[i][0];
-                int Bcolumn = boxes[i][1];
+                int Brow = level.Row(boxes[i]);
+                int Bcolumn = level.Col(boxes[i]);
 
                 // Manhattan distance formula
                 int dManhattan = Math.Abs(Trow - Brow) + Math.Abs(Tcolumn - Bcolumn);
@@ -139,7 +218,7 @@ public class Node {
 
     // Concatenate every variable of a node into a string and prints it
     public void PrintNode() {
-        float roundedValueNode = (float)Math.Round(valueNode, 2, MidpointRounding.AwayFromZero);
+        double roundedValueNode = Math.Round(valueNode, 2, MidpointRounding.AwayFromZero);
 
         string result = $"{idNode},{stateNode.Id},{(parentNode != null ? parentNode.idNode.ToString() : "None")},{action},{depth},{cost:F2},{heuristic:F2},{roundedValueNode:F2}";
         Console.WriteLine(result);
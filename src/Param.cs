@@ -1,25 +1,216 @@
 using System;
 using System.Collections.Generic;
+using System.Globalization;
 
 namespace Sokoban;
 
 public class Param {
+    private string command = "";
     private string level;
-    private string strategy;
-    private string depth;
+    private string strategy = "";
+    private string depth = "";
+    private string moves = "";
+    private bool pushLevel;
+    private bool normalizePlayer;
+    private bool tunnels;
+    private string heuristic = "MANHATTAN";
+    private float weight = 1.0f;
+    private string objective = "MOVES";
+    private string levelSelector = "1";
+    private string levelTitle = "";
+    private LevelFile? levelFile;
+    private string collectionTitle = "";
+    private bool printSolution;
+    private bool headless;
+    private bool jsonStatistics;
+    private bool optimize;
+    private bool rleSolution;
+    private string exportPath = "";
+    private string csvPath = "";
+    private double timeLimit;
+    private int nodeLimit;
+    private int memoryLimit;
 
     // Param constructor
     public Param(string[] args) {
-        level = args[0];
-        strategy = args[1];
-        depth = args[2];
+        int firstOption = 3;
 
-        // Parse newlines properly in level string
-        level = level.Replace("\\n", "\n");
+        // verify '<level>'|<file> <lurd> replays a solution instead of searching one
+        if (args[0] == "verify") {
+            command = args[0];
+            level = args[1];
+            moves = args[2];
+        }
+        // batch <file> <strategy> <depth> solves every level of a collection
+        else if (args[0] == "batch") {
+            if (args.Length < 4) {
+                throw new ArgumentException("Required arguments not found.");
+            }
+            command = args[0];
+            level = args[1];
+            strategy = args[2];
+            depth = args[3];
+            firstOption = 4;
+        }
+        else {
+            level = args[0];
+            strategy = args[1];
+            depth = args[2];
+        }
+
+        // Parse optional flags
+        for (int i = firstOption; i < args.Length; i++) {
+            switch (args[i]) {
+                case "--push":
+                    pushLevel = true;
+                    break;
+
+                case "--normalize":
+                    normalizePlayer = true;
+                    break;
+
+                case "--tunnels":
+                    tunnels = true;
+                    break;
+
+                case "--heuristic":
+                    heuristic = NextValue(args, ref i);
+                    break;
+
+                case "--objective":
+                    objective = NextValue(args, ref i);
+                    break;
+
+                case "--weight":
+                    weight = float.Parse(NextValue(args, ref i), CultureInfo.InvariantCulture);
+                    break;
+
+                case "--level":
+                    levelSelector = NextValue(args, ref i);
+                    break;
+
+                case "--headless":
+                    headless = true;
+                    break;
+
+                case "--optimize":
+                    optimize = true;
+                    break;
+
+                case "--json":
+                    jsonStatistics = true;
+                    break;
+
+                case "--print":
+                    printSolution = true;
+                    break;
+
+                case "--rle":
+                    rleSolution = true;
+                    break;
+
+                case "--export":
+                    exportPath = NextValue(args, ref i);
+                    break;
+
+                case "--csv":
+                    csvPath = NextValue(args, ref i);
+                    break;
+
+                case "--time":
+                    timeLimit = double.Parse(NextValue(args, ref i), CultureInfo.InvariantCulture);
+                    break;
+
+                case "--nodes":
+                    nodeLimit = int.Parse(NextValue(args, ref i));
+                    break;
+
+                case "--memory":
+                    memoryLimit = int.Parse(NextValue(args, ref i));
+                    break;
+
+                default:
+                    throw new ArgumentException("Unknown option: " + args[i]);
+            }
+        }
+
+        // Batch mode keeps the collection path, its levels are loaded one by one
+        if (command == "batch") {
+            if (!File.Exists(level)) {
+                throw new ArgumentException("Collection file not found: " + level);
+            }
+            collectionTitle = Path.GetFileNameWithoutExtension(level);
+        }
+        // Load the level from a collection file (.xsb / .sok / .txt / .slc) or parse it from the argument
+        else if (File.Exists(level)) {
+            collectionTitle = Path.GetFileNameWithoutExtension(level);
+            levelFile = LevelFile.Select(LoadCollection(level), levelSelector);
+            level = levelFile.Level;
+            levelTitle = levelFile.Title;
+        }
+        else {
+            // Parse newlines properly in level string
+            level = level.Replace("\\n", "\n");
+
+            // Run-length encoded levels ("4#|#@$.#") as found in public collections
+            if (Rle.IsEncodedLevel(level)) {
+                level = Rle.DecodeLevel(level);
+            }
+        }
+
+        // Step-level nodes walk the player, collapsing the player position would prune every walk
+        if (normalizePlayer && !pushLevel) {
+            throw new ArgumentException("--normalize requires --push.");
+        }
+
+        if (weight < 1.0f) {
+            throw new ArgumentException("--weight must be at least 1.");
+        }
+
+        if (timeLimit < 0 || nodeLimit < 0 || memoryLimit < 0) {
+            throw new ArgumentException("--time, --nodes and --memory must not be negative.");
+        }
+    }
+
+    // Returns the levels of a collection file, SLC files are read as XML
+    public static List<LevelFile> LoadCollection(string path) {
+        if (Path.GetExtension(path).Equals(".slc", StringComparison.OrdinalIgnoreCase)) {
+            return Slc.Load(path);
+        }
+        return LevelFile.Load(path);
+    }
+
+    // Returns the value following an option
+    private string NextValue(string[] args, ref int i) {
+        if (i + 1 >= args.Length) {
+            throw new ArgumentException("Missing value for option: " + args[i]);
+        }
+        return args[++i];
     }
 
     // Properties
-    public string Level => level;               // Level Getter
+    public string Command => command;          // Subcommand Getter (empty when searching)
+    public string Level => level;               // Level Getter (collection path in batch mode)
+    public string Moves => moves;              // LURD moves Getter (verify)
+    public string LevelTitle => levelTitle;    // Level title Getter (empty if not loaded from a file)
+    public LevelFile? LevelFile => levelFile;  // Selected level of the collection file Getter (null if not loaded from a file)
+    public string CollectionTitle => collectionTitle; // Collection file name Getter (empty if not loaded from a file)
     public string Strategy => strategy;        // Strategy Getter
     public string Depth => depth;              // Depth Getter
+    public bool PushLevel => pushLevel;        // Push-level search Getter
+    public bool NormalizePlayer => normalizePlayer; // Normalized player Getter
+    public bool Tunnels => tunnels;            // Tunnel macro pushes Getter
+    public string Heuristic => heuristic;      // Heuristic Getter
+    public float Weight => weight;             // Heuristic weight Getter (WA*)
+    public string Objective => objective;      // Optimised metric Getter (MOVES / PUSHES / MOVES_PUSHES / PUSHES_MOVES)
+    public bool PrintSolution => printSolution; // LURD solution printing Getter
+    public bool Headless => headless;          // Headless mode Getter (no window)
+    public bool JsonStatistics => jsonStatistics; // JSON statistics Getter
+    public bool Optimize => optimize;          // Solution optimizer Getter
+    public bool RleSolution => rleSolution;    // Run-length encoded solution Getter
+    public string ExportPath => exportPath;    // SLC export path Getter (empty if not exported)
+    public string CsvPath => csvPath;          // Batch CSV path Getter (empty to print it)
+    public double TimeLimit => timeLimit;      // Wall-clock limit Getter (seconds, 0 if unlimited)
+    public int NodeLimit => nodeLimit;         // Expanded nodes limit Getter (0 if unlimited)
+    public int MemoryLimit => memoryLimit;     // Managed heap limit Getter (MB, 0 if unlimited)
 }
@@ -1,86 +1,373 @@
 ﻿using System;
 using Raylib_cs;
 using System.Data;
+using System.Globalization;
 using System.Security;
+using System.Text;
 
 namespace Sokoban;
 
 class Program {
-    public static void Main(string[] args) {
+
+    // Exit codes
+    private const int EXIT_SOLVED = 0;
+    private const int EXIT_NO_SOLUTION = 1;
+    private const int EXIT_LIMIT_REACHED = 2;
+
+    // Solution optimizer: pushes per re-searched window and states explored per window
+    private const int OPTIMIZER_WINDOW = 4;
+    private const int OPTIMIZER_STATES = 5000;
+
+    // Most target subsets BIDIR seeds its backward search with (one goal configuration per subset)
+    private const int MAX_GOAL_SUBSETS = 10000;
+
+    public static int Main(string[] args) {
 
         // Check if there are not arguments (True = run GUI / False = parse arguments)
         if (args.Length == 0) {
             // Renderer rendererGUI = new Renderer();
+            return EXIT_SOLVED;
         }
         else {
 
             if (args.Length > 0 && args.Length < 3) {
-                Console.WriteLine("\nUsage: ./sokoban.exe '<level>' <strategy> <depth>\n");
+                Console.WriteLine("\nUsage: ./sokoban.exe '<level>'|<file> <strategy> <depth> [--level <index|title>] [--print] [--rle] [--export <file.slc>] [--headless] [--json] [--time <s>] [--nodes <n>] [--memory <MB>] [--push] [--normalize] [--heuristic <name>] [--weight <w>] [--objective <name>] [--optimize] [--tunnels]");
+                Console.WriteLine("       ./sokoban.exe verify '<level>'|<file> <lurd> [--level <index|title>]");
+                Console.WriteLine("       ./sokoban.exe batch <file> <strategy> <depth> [--time <s>] [--nodes <n>] [--memory <MB>] [--csv <file.csv>] [search options]\n");
                 throw new ArgumentException("Required arguments not found.");
             }
 
             Param param = new Param(args);
 
-            // Valid characters defined
-            HashSet<char> validCharacters = new HashSet<char> { '#', '@', '$', '.', '*', '+', ' ', '\n' };
+            // Solve every level of a collection file
+            if (param.Command == "batch") {
+                CheckSearchParam(param);
+                return BatchSolve(param);
+            }
 
-            // Check if all characters in the level string are valid
-            foreach (char c in param.Level) {
-                if (!validCharacters.Contains(c)) {
-                    throw new InvalidOperationException("Character not valid: " + c);
-                }
+            (Level level, State state) = CreateLevel(param.Level);
+
+            // Replay a LURD solution (run counts allowed) with the movement rules of the search
+            if (param.Command == "verify") {
+                Verifier verifier = Verifier.Decode(level, state, param.Moves);
+                verifier.PrintReport();
+                return verifier.Solved ? EXIT_SOLVED : EXIT_NO_SOLUTION;
             }
 
-            Level level = new Level(param.Level);
-            State state = new State(param.Level);
+            CheckSearchParam(param);
 
-            // Check if there is a different number of boxes and targets
-            if (state.Boxes.Length > level.Targets.Length) {
-                throw new InvalidOperationException("The level must have at least as many targets as boxes");
+            if (param.LevelTitle != "") {
+                Console.WriteLine("Level: " + param.LevelTitle);
             }
 
-            // Check if there are not at least one box and one target
-            if (state.Boxes.Length == 0 || level.Targets.Length == 0) {
-                throw new InvalidOperationException("The level must have at least one box and one target");
+            // ProblemDomain(level, state);
+            SearchResult result = Search(level, state, param);
+            SearchStatistics statistics = result.Statistics;
+            List<Node>? solutionPath = result.SolutionPath;
+
+            if (param.JsonStatistics) {
+                statistics.PrintJson();
+            }
+            else {
+                statistics.PrintStatistics();
             }
 
-            HashSet<string> validStrategies = new HashSet<string> { "BFS", "DFS", "UC", "GREEDY", "A*" };
+            if (!result.Solved) {
+                Console.WriteLine(result.Describe());
+            }
 
-            if (!validStrategies.Contains(param.Strategy)) {
-                Console.WriteLine("\nValid strategies: BFS / DFS / UC / GREEDY / A*\n");
-                throw new ArgumentException("Invalid strategy.");
+            if (result.Outcome == SearchOutcome.LimitReached) {
+                PrintBestNode(level, result.BestNode, param);
             }
 
-            // Parse strategy (optional)
-            if (param.Strategy != null) {
-                switch (param.Strategy) {
-                    case "BFS":
-                        break;
+            // Report the achieved solution length (one node per step after the root)
+            if (solutionPath != null) {
 
-                    case "DFS":
-                        break;
+                // Shorten the solution found by the search before reporting it
+                if (param.Optimize) {
+                    string foundMoves = SolutionMoves(solutionPath);
+                    string optimizedMoves = OptimizeSolution(level, solutionPath[0].StateNode, foundMoves);
+                    Console.WriteLine("Optimized: " + foundMoves.Length + " -> " + optimizedMoves.Length + " moves, " + PushesCount(foundMoves) + " -> " + PushesCount(optimizedMoves) + " pushes");
+                    solutionPath = MovesPath(level, solutionPath[0], optimizedMoves);
+                }
+
+                if (param.Strategy == "WA*") {
+                    Console.WriteLine("Weight: " + param.Weight.ToString(CultureInfo.InvariantCulture));
+                }
+                Console.WriteLine("Solution length: " + (solutionPath.Count - 1) + " moves");
+
+                // Report the metric the search optimised when it is not the solution length above
+                // (only UC, A* and IDA* guarantee it is minimal)
+                if (param.Objective != "MOVES") {
+                    string solutionMoves = SolutionMoves(solutionPath);
+                    Console.WriteLine("Objective: " + param.Objective + " (" + solutionMoves.Length + " moves, " + PushesCount(solutionMoves) + " pushes)");
+                }
+
+                // Print the LURD solution (lower-case moves, upper-case pushes)
+                if (param.PrintSolution || param.RleSolution) {
+                    string moves = SolutionMoves(solutionPath);
+                    Console.WriteLine("Solution: " + (param.RleSolution ? Rle.Encode(moves) : moves));
+                    Console.WriteLine("Moves: " + moves.Length);
+                    Console.WriteLine("Pushes: " + PushesCount(moves));
+                }
+
+                // Export the solved level to a SLC collection, keeping the metadata of its collection file
+                // Levels given on the command line are named after the export file
+                if (param.ExportPath != "") {
+                    string exportName = Path.GetFileNameWithoutExtension(param.ExportPath);
+                    LevelFile solvedLevel = param.LevelFile ?? new LevelFile(param.Level, exportName);
+                    solvedLevel.Solution = SolutionMoves(solutionPath);
+                    string title = param.CollectionTitle != "" ? param.CollectionTitle : exportName;
+                    Slc.Save(param.ExportPath, title, new List<LevelFile> { solvedLevel });
+                }
+
+                // With more targets than boxes some targets are left empty
+                int[][] emptyTargets = EmptyTargets(level, solutionPath[solutionPath.Count - 1].StateNode);
+
+                if (emptyTargets.Length > 0) {
+                    Console.Write("Empty targets: ");
+                    PrintCoordsArray(emptyTargets);
+                }
+            }
+
+            if (solutionPath == null) {
+                return result.Outcome == SearchOutcome.Unsolvable ? EXIT_NO_SOLUTION : EXIT_LIMIT_REACHED;
+            }
+
+            // Headless mode skips the window (CI servers, scripts)
+            if (!param.Headless) {
+                Renderer rendererCLI = new Renderer(level, state);
+                rendererCLI.Render(solutionPath);
+            }
+
+            return EXIT_SOLVED;
+        }
+    } // End Main
+
+    // Returns the level and its initial state, checking the characters and the number of boxes and targets
+    internal static (Level, State) CreateLevel(string levelString) {
+
+        // Valid characters defined
+        HashSet<char> validCharacters = new HashSet<char> { '#', '@', '$', '.', '*', '+', ' ', '\n' };
+
+        // Check if all characters in the level string are valid
+        foreach (char c in levelString) {
+            if (!validCharacters.Contains(c)) {
+                throw new InvalidOperationException("Character not valid: " + c);
+            }
+        }
+
+        Level level = new Level(levelString);
+        State state = new State(levelString, level);
+
+        // Check if there is a different number of boxes and targets
+        if (state.Boxes.Length > level.Targets.Length) {
+            throw new InvalidOperationException("The level must have at least as many targets as boxes");
+        }
+
+        // Check if there are not at least one box and one target
+        if (state.Boxes.Length == 0 || level.Targets.Length == 0) {
+            throw new InvalidOperationException("The level must have at least one box and one target");
+        }
+
+        return (level, state);
+    }
+
+    // Checks the strategy and heuristic of the search
+    static void CheckSearchParam(Param param) {
+
+        HashSet<string> validStrategies = new HashSet<string> { "BFS", "DFS", "UC", "GREEDY", "A*", "WA*", "IDDFS", "IDA*", "BIDIR" };
+
+        if (!validStrategies.Contains(param.Strategy)) {
+            Console.WriteLine("\nValid strategies: BFS / DFS / UC / GREEDY / A* / WA* / IDDFS / IDA* / BIDIR\n");
+            throw new ArgumentException("Invalid strategy.");
+        }
+
+        // The backward search starts from the goal configurations one step at a time
+        if (param.Strategy == "BIDIR" && param.PushLevel) {
+            throw new ArgumentException("BIDIR requires step-level search.");
+        }
+
+        HashSet<string> validHeuristics = new HashSet<string> { "MANHATTAN", "PUSH", "MATCHING" };
+
+        if (!validHeuristics.Contains(param.Heuristic)) {
+            Console.WriteLine("\nValid heuristics: MANHATTAN / PUSH / MATCHING\n");
+            throw new ArgumentException("Invalid heuristic.");
+        }
+
+        HashSet<string> validObjectives = new HashSet<string> { "MOVES", "PUSHES", "MOVES_PUSHES", "PUSHES_MOVES" };
 
-                    case "UC":
+        if (!validObjectives.Contains(param.Objective)) {
+            Console.WriteLine("\nValid objectives: MOVES / PUSHES / MOVES_PUSHES / PUSHES_MOVES\n");
+            throw new ArgumentException("Invalid objective.");
+        }
+    }
+
+    // Solves every level of a collection file within the time and node limits, printing a summary table and CSV.
+    // The solved levels can be exported to a SLC collection.
+    // Returns the solved exit code only if every level was solved.
+    static int BatchSolve(Param param) {
+
+        List<LevelFile> levels = Param.LoadCollection(param.Level);
+        List<LevelFile> solvedLevels = new List<LevelFile>();
+        List<string[]> rows = new List<string[]>();
+        int solvedCount = 0;
+        long totalTime = 0;
+
+        for (int i = 0; i < levels.Count; i++) {
+            string title = levels[i].Title != "" ? levels[i].Title : (i + 1).ToString();
+            string status;
+            string moves = "";
+            string pushes = "";
+            string nodes = "";
+            string time = "";
+
+            try {
+                (Level level, State state) = CreateLevel(levels[i].Level);
+                SearchResult result = Search(level, state, param);
+
+                switch (result.Outcome) {
+                    case SearchOutcome.Solved:
+                        string solution = SolutionMoves(result.SolutionPath!);
+
+                        if (param.Optimize) {
+                            solution = OptimizeSolution(level, state, solution);
+                        }
+
+                        status = "solved";
+                        moves = solution.Length.ToString();
+                        pushes = PushesCount(solution).ToString();
+                        solvedCount++;
+
+                        levels[i].Title = title;
+                        levels[i].Solution = solution;
+                        solvedLevels.Add(levels[i]);
                         break;
 
-                    case "GREEDY":
+                    case SearchOutcome.Unsolvable:
+                        status = "unsolvable";
                         break;
 
-                    case "A*":
+                    case SearchOutcome.Inconclusive:
+                        status = "depth";
                         break;
 
                     default:
-                        Console.WriteLine("Unknown parameter: " + param.Strategy);
+                        status = "limit";
                         break;
                 }
+
+                nodes = result.Statistics.TotalNodes.ToString();
+                time = result.Statistics.Elapsed.TotalMilliseconds.ToString("0", CultureInfo.InvariantCulture);
+                totalTime += (long)result.Statistics.Elapsed.TotalMilliseconds;
+            }
+            catch (InvalidOperationException e) {
+                // Invalid levels are reported without stopping the batch
+                status = "invalid";
+                Console.WriteLine("Level " + title + ": " + e.Message);
             }
 
-            // ProblemDomain(level, state);
-            List<Node> solutionPath = SearchAlgorithm(level, state, param.Depth, param.Strategy);
-            Renderer rendererCLI = new Renderer(level, state);
-            rendererCLI.Render(solutionPath);
+            rows.Add(new string[] { (i + 1).ToString(), title, status, moves, pushes, nodes, time });
         }
-    } // End Main
+
+        string[] header = { "Index", "Title", "Status", "Moves", "Pushes", "Nodes", "Time (ms)" };
+
+        PrintTable(header, rows);
+        Console.WriteLine("Solved: " + solvedCount + " / " + levels.Count + " in " + totalTime + " ms");
+
+        // Comma-separated values, quoting titles that contain commas or quotes
+        List<string> csv = new List<string> { "index,title,status,moves,pushes,nodes,time_ms" };
+
+        foreach (var row in rows) {
+            csv.Add(string.Join(",", row.Select(CsvField)));
+        }
+
+        if (param.CsvPath != "") {
+            File.WriteAllLines(param.CsvPath, csv);
+        }
+        else {
+            Console.WriteLine();
+            csv.ForEach(Console.WriteLine);
+        }
+
+        // Export the solved levels with their solutions and metadata as one SLC collection
+        if (param.ExportPath != "") {
+            Slc.Save(param.ExportPath, param.CollectionTitle, solvedLevels);
+        }
+
+        return solvedCount == levels.Count ? EXIT_SOLVED : EXIT_NO_SOLUTION;
+    }
+
+    // Prints rows as a table with aligned columns
+    static void PrintTable(string[] header, List<string[]> rows) {
+
+        int[] widths = new int[header.Length];
+
+        for (int col = 0; col < header.Length; col++) {
+            widths[col] = Math.Max(header[col].Length, rows.Select(row => row[col].Length).DefaultIfEmpty(0).Max());
+        }
+
+        Console.WriteLine(string.Join(" | ", header.Select((field, col) => field.PadRight(widths[col]))));
+        Console.WriteLine(string.Join("-+-", widths.Select(width => new string('-', width))));
+
+        foreach (var row in rows) {
+            Console.WriteLine(string.Join(" | ", row.Select((field, col) => field.PadRight(widths[col]))));
+        }
+    }
+
+    // Returns a CSV field, quoted if needed
+    internal static string CsvField(string field) {
+        if (field.Contains(',') || field.Contains('"')) {
+            return "\"" + field.Replace("\"", "\"\"") + "\"";
+        }
+        return field;
+    }
+
+    // Returns if the search ran out of its time, node or memory budget, storing which one in the statistics.
+    // Memory is the managed heap high-water mark sampled during the search, so it is approximate.
+    static bool BudgetExceeded(Param param, SearchStatistics statistics) {
+
+        if (param.TimeLimit > 0 && statistics.Elapsed.TotalSeconds >= param.TimeLimit) {
+            statistics.ExceededBudget = "time";
+        }
+        else if (param.NodeLimit > 0 && statistics.NodesExpanded >= param.NodeLimit) {
+            statistics.ExceededBudget = "nodes";
+        }
+        else if (param.MemoryLimit > 0 && statistics.PeakMemory >= (long)param.MemoryLimit * 1024 * 1024) {
+            statistics.ExceededBudget = "memory";
+        }
+
+        return statistics.ExceededBudget != "";
+    }
+
+    // Returns the sum of the push distances from every box to its nearest target (0 once every box is on a target)
+    static int RemainingDistance(Level level, State state) {
+
+        int distance = 0;
+
+        foreach (var box in state.Boxes) {
+            distance += level.NearestPushDistance(box);
+        }
+
+        return distance;
+    }
+
+    // Prints the best partial node of a search stopped by a budget
+    static void PrintBestNode(Level level, Node? bestNode, Param param) {
+
+        if (bestNode == null) {
+            return;
+        }
+
+        int boxesOnTargets = bestNode.StateNode.Boxes.Count(box => level.IsTarget(box));
+        string moves = SolutionMoves(BuildSolutionPath(level, bestNode, param));
+
+        Console.WriteLine("Best partial state: " + boxesOnTargets + " of " + bestNode.StateNode.Boxes.Length + " boxes on targets after " + moves.Length + " moves");
+
+        if (param.PrintSolution || param.RleSolution) {
+            Console.WriteLine("Partial solution: " + (param.RleSolution ? Rle.Encode(moves) : moves));
+        }
+    }
 
     // Prints ID, rows, columns, walls, targets, player and boxes positions in the level
     static void ProblemDomain(Level level, State state) {
@@ -92,168 +379,428 @@ class Program {
         PrintCoordsArray(level.Walls);
         Console.Write("\tTargets: ");
         PrintCoordsArray(level.Targets);
-        Console.Write("\tPlayer: (" + state.Player.Item1 + "," + state.Player.Item2 + ")");
+        Console.Write("\tPlayer: (" + level.Row(state.Player) + "," + level.Col(state.Player) + ")");
         Console.Write("\n\tBoxes: ");
-        PrintCoordsArray(state.Boxes);
+        PrintCoordsArray(state.Boxes.Select(box => new int[] { level.Row(box), level.Col(box) }).ToArray());
     }
 
     // Returns the list of successors, which are all the possible moves that the player can make in a given state
-    static List<Successor> SuccessorFunction(Level level, State state) {
+    static List<Successor> SuccessorFunction(Level level, State state, bool tunnels) {
 
         List<Successor> successors = new List<Successor>();
 
-        (int, int) playerMove;
-        (int, int) boxMove;
+        int playerMove;
+        int boxMove;
         int cost = 1;
 
-        // Store direction coordinates
-        var directions = new (int, int)[] { (-1, 0), (0, 1), (1, 0), (0, -1), };
+        // Store direction offsets and actions (clock-wise)
+        int[] directions = level.Directions;
+        string moves = "urdl";
+        string pushes = "URDL";
 
-        // Hashsets of walls and boxes array to check faster
-        HashSet<(int, int)> wallsSet = new HashSet<(int, int)>();
-        HashSet<(int, int)> boxesSet = new HashSet<(int, int)>();
+        // Loop to check every direction (clock-wise)
+        for (int i = 0; i < directions.Length; i++) {
+            playerMove = state.Player + directions[i];
 
-        foreach (var wall in level.Walls) {
-            wallsSet.Add((wall[0], wall[1]));
+            // Check wall collision
+            if (level.IsWall(playerMove)) {
+                continue;
+            }
+
+            // Check box collision
+            if (state.HasBox(playerMove)) {
+                boxMove = playerMove + directions[i];
+
+                // Check if new box position is empty and not a dead square
+                if (!level.IsWall(boxMove) && !state.HasBox(boxMove) && !level.IsDeadSquare(boxMove)) {
+                    Successor push = new Successor(pushes[i].ToString(), state.PushBox(level, playerMove, boxMove), cost);
+                    successors.Add(tunnels ? TunnelMacro(level, push, boxMove, directions[i]) : push);
+                }
+            }
+            else {
+                successors.Add(new Successor(moves[i].ToString(), state.MovePlayer(level, playerMove), cost));
+            }
         }
 
-        foreach (var box in state.Boxes) {
-            boxesSet.Add((box[0], box[1]));
+        return successors;
+    }
+
+    // Returns the list of push successors: every box push reachable by walking, as one macro action (walk + push)
+    static List<Successor> PushSuccessorFunction(Level level, State state, bool tunnels) {
+
+        List<Successor> successors = new List<Successor>();
+
+        // Store direction offsets and push actions (clock-wise)
+        int[] directions = level.Directions;
+        string pushes = "URDL";
+
+        // Cells the player can walk to without pushing any box
+        int[] reachable = level.ReachableRegion(state);
+
+        for (int cell = 0; cell < reachable.Length; cell++) {
+            if (reachable[cell] == -1) {
+                continue;
+            }
+
+            for (int i = 0; i < directions.Length; i++) {
+                int boxCell = cell + directions[i];
+                int boxMove = boxCell + directions[i];
+
+                // Check there is a box to push and its new position is empty and not a dead square
+                if (!state.HasBox(boxCell)) {
+                    continue;
+                }
+
+                if (level.IsWall(boxMove) || state.HasBox(boxMove) || level.IsDeadSquare(boxMove)) {
+                    continue;
+                }
+
+                string action = WalkPath(level, reachable, state.Player, cell) + pushes[i];
+                Successor push = new Successor(action, state.PushBox(level, boxCell, boxMove), action.Length);
+                successors.Add(tunnels ? TunnelMacro(level, push, boxMove, directions[i]) : push);
+            }
+        }
+
+        return successors;
+    }
+
+    // Returns the push successor extended with the pushes that carry the box on through a one-wide tunnel.
+    // Only pushes made with the player inside the tunnel are extended (the usual safe tunnel macro condition),
+    // so a box pushed into a tunnel from outside can still be parked on its first cell.
+    // The box stops on a target, in front of a wall, another box or a dead square, and once it leaves the tunnel.
+    static Successor TunnelMacro(Level level, Successor push, int box, int direction) {
+
+        if (!level.IsTunnel(box - direction, direction)) {
+            return push;
         }
 
-        // Copy of boxes array
-        int[][] newBoxes = new int[state.Boxes.Length][];
-        for (int i = 0; i < state.Boxes.Length; i++) {
-            newBoxes[i] = new int[state.Boxes[i].Length];
-            Array.Copy(state.Boxes[i], newBoxes[i], state.Boxes[i].Length);
+        string action = push.Action;
+        char pushMove = action[action.Length - 1];
+        State state = push.State;
+
+        while (level.IsTunnel(box, direction) && !level.IsTarget(box)) {
+            int boxMove = box + direction;
+
+            if (level.IsWall(boxMove) || state.HasBox(boxMove) || level.IsDeadSquare(boxMove)) {
+                break;
+            }
+
+            state = state.PushBox(level, box, boxMove);
+            action += pushMove;
+            box = boxMove;
         }
 
-        // Loop to check every direction (clock-wise)
+        return new Successor(action, state, push.Cost + action.Length - push.Action.Length);
+    }
+
+    // Returns the shortest walk (lower-case moves) from the player to a reachable cell
+    static string WalkPath(Level level, int[] parents, int player, int cell) {
+
+        List<char> moves = new List<char>();
+        string walks = "urdl";
+
+        while (cell != player) {
+            int parent = parents[cell];
+            moves.Add(walks[Array.IndexOf(level.Directions, cell - parent)]);
+            cell = parent;
+        }
+        moves.Reverse();
+
+        return new string(moves.ToArray());
+    }
+
+    // Returns the list of backward successors: the player walks or pulls the box behind it.
+    // Actions are stored as the forward move that undoes them (lower-case walks, upper-case pushes).
+    static List<Successor> PullSuccessorFunction(Level level, State state) {
+
+        List<Successor> successors = new List<Successor>();
+        int cost = 1;
+
+        // Store direction offsets and actions (clock-wise)
+        int[] directions = level.Directions;
+        string moves = "urdl";
+        string pushes = "URDL";
+
         for (int i = 0; i < directions.Length; i++) {
-            playerMove = (state.Player.Item1 + directions[i].Item1, state.Player.Item2 + directions[i].Item2);
+            int playerMove = state.Player + directions[i];
+            int boxCell = state.Player - directions[i];
+            int opposite = (i + 2) % directions.Length;
 
-            // Check wall collision
-            if (!wallsSet.Contains(playerMove)) {
-
-                State sucState = new State(playerMove, newBoxes);
-                Successor successor = new Successor("NOTHING", sucState, 0);
-
-                // Check box collision
-                if (boxesSet.Contains(playerMove)) {
-                    // Box collision
-
-                    for (int j = 0; j < newBoxes.Length; j++) {
-                        boxMove.Item1 = newBoxes[j][0] + directions[i].Item1;
-                        boxMove.Item2 = newBoxes[j][1] + directions[i].Item2;
-
-                        // Check if new box position is empty
-                        if (!wallsSet.Contains(boxMove) && !boxesSet.Contains(boxMove)) {
-                            // Find the box that is getting pushed by the player
-                            if (newBoxes[j][0] == playerMove.Item1 && newBoxes[j][1] == playerMove.Item2) {
-
-                                sucState.MovePlayer(newBoxes[j][0], newBoxes[j][1]);
-
-                                newBoxes[j][0] += directions[i].Item1;
-                                newBoxes[j][1] += directions[i].Item2;
-
-                                // Sort coordinates (disabled as it was causing rendering issues)
-                                sucState.Boxes = newBoxes
-                                        // .OrderBy(box => box[0])
-                                        // .ThenBy(box => box[1])
-                                        .Select(box => (int[])box.Clone()) // Deep copy each box
-                                        .ToArray();
-
-                                // Calculate new state id
-                                sucState.Id = sucState.CalculateMD5Hash(sucState.Player, sucState.Boxes);
-
-                                switch (i) {
-                                    case 0:
-                                        successor.Action = "U";
-                                        successor.Cost = cost;
-                                        successors.Add(successor);
-                                        break;
-                                    case 1:
-                                        successor.Action = "R";
-                                        successor.Cost = cost;
-                                        successors.Add(successor);
-                                        break;
-                                    case 2:
-                                        successor.Action = "D";
-                                        successor.Cost = cost;
-                                        successors.Add(successor);
-                                        break;
-                                    case 3:
-                                        successor.Action = "L";
-                                        successor.Cost = cost;
-                                        successors.Add(successor);
-                                        break;
-                                }
-
-                                // Revert boxes move to reset to original coords
-                                newBoxes[j][0] -= directions[i].Item1;
-                                newBoxes[j][1] -= directions[i].Item2;
-                            }
-                        }
-                    }
+            // Check wall and box collision
+            if (level.IsWall(playerMove) || state.HasBox(playerMove)) {
+                continue;
+            }
+
+            successors.Add(new Successor(moves[opposite].ToString(), state.MovePlayer(level, playerMove), cost));
+
+            // The box behind the player can be pulled along
+            if (state.HasBox(boxCell)) {
+                successors.Add(new Successor(pushes[opposite].ToString(), state.PullBox(level, boxCell, state.Player), cost));
+            }
+        }
+
+        return successors;
+    }
+
+    // Returns the goal states: the boxes on every subset of targets (one subset when there are as many targets
+    // as boxes) and the player in each region it could end up in
+    internal static List<State> GoalStates(Level level, State state) {
+
+        List<State> goalStates = new List<State>();
+        short[] targetCells = level.Targets
+            .Select(target => (short)level.Cell(target[0], target[1]))
+            .OrderBy(target => target)
+            .ToArray();
+
+        // Level interior: cells the player could walk to if there were no boxes
+        int[] interior = level.ReachableRegion(new State(state.Player, Array.Empty<short>(), 0, level));
+
+        foreach (var goalBoxes in TargetSubsets(targetCells, state.Boxes.Length)) {
+            ulong boxesHash = level.ZobristBoxesHash(goalBoxes);
+            bool[] assigned = new bool[interior.Length];
+
+            for (int cell = 0; cell < interior.Length; cell++) {
+                if (interior[cell] == -1 || assigned[cell] || Array.BinarySearch(goalBoxes, (short)cell) >= 0) {
+                    continue;
                 }
-                else {
-                    // No box collision
-                    sucState.Id = sucState.CalculateMD5Hash(playerMove, newBoxes);
-
-                    switch (i) {
-                        case 0:
-                            successor.Action = "u";
-                            successor.Cost = cost;
-                            successors.Add(successor);
-                            break;
-                        case 1:
-                            successor.Action = "r";
-                            successor.Cost = cost;
-                            successors.Add(successor);
-                            break;
-                        case 2:
-                            successor.Action = "d";
-                            successor.Cost = cost;
-                            successors.Add(successor);
-                            break;
-                        case 3:
-                            successor.Action = "l";
-                            successor.Cost = cost;
-                            successors.Add(successor);
-                            break;
+
+                State goalState = new State(cell, goalBoxes, boxesHash, level);
+                int[] region = level.ReachableRegion(goalState);
+
+                for (int regionCell = 0; regionCell < region.Length; regionCell++) {
+                    if (region[regionCell] != -1) {
+                        assigned[regionCell] = true;
                     }
                 }
+
+                goalStates.Add(goalState);
             }
         }
 
-        return successors;
+        return goalStates;
     }
 
-    // Returns if the objective function (all boxes are on targets) is achieved.
-    static bool ObjectiveFunction(Level level, State state) {
+    // Returns the number of target subsets holding every box (targets choose boxes), capped at long.MaxValue
+    internal static long GoalSubsetsCount(int targets, int boxes) {
 
-        HashSet<(int, int)> boxesSet = new HashSet<(int, int)>();
-        HashSet<(int, int)> targetsSet = new HashSet<(int, int)>();
+        long count = 1;
 
-        foreach (var box in state.Boxes) {
-            boxesSet.Add((box[0], box[1]));
+        for (int i = 1; i <= boxes; i++) {
+            // count * (targets - boxes + i) / i stays exact as the running product is always a binomial coefficient
+            if (count > long.MaxValue / (targets - boxes + i)) {
+                return long.MaxValue;
+            }
+            count = count * (targets - boxes + i) / i;
         }
 
-        foreach (var target in level.Targets) {
-            targetsSet.Add((target[0], target[1]));
+        return count;
+    }
+
+    // Returns every sorted subset of the target cells with the given size
+    static List<short[]> TargetSubsets(short[] targetCells, int size) {
+
+        List<short[]> subsets = new List<short[]>();
+        ChooseTargets(targetCells, new short[size], 0, 0, subsets);
+        return subsets;
+    }
+
+    // Fills the subset from the given index with targets from the start position onwards
+    static void ChooseTargets(short[] targetCells, short[] subset, int start, int index, List<short[]> subsets) {
+
+        if (index == subset.Length) {
+            subsets.Add((short[])subset.Clone());
+            return;
         }
 
-        bool allBoxesOnTarget = boxesSet.IsSubsetOf(targetsSet);
+        for (int i = start; i <= targetCells.Length - (subset.Length - index); i++) {
+            subset[index] = targetCells[i];
+            ChooseTargets(targetCells, subset, i + 1, index + 1, subsets);
+        }
+    }
 
-        if (allBoxesOnTarget) {
-            state.CalculateMD5Hash(state.Player, state.Boxes);
+    // Returns the moves of a solution path (one action per node after the root)
+    internal static string SolutionMoves(List<Node> solutionPath) {
+        return string.Concat(solutionPath.Skip(1).Select(node => node.Action));
+    }
+
+    // Returns the number of pushes (upper-case moves) of a LURD solution
+    internal static int PushesCount(string moves) {
+        return moves.Count(char.IsUpper);
+    }
+
+    // Returns the coordinates of the targets without a box
+    static int[][] EmptyTargets(Level level, State state) {
+        return level.Targets
+            .Where(target => !state.HasBox(level.Cell(target[0], target[1])))
+            .ToArray();
+    }
+
+    // Returns the state reached after a single player move (upper-case moves push the box in front of the player)
+    static State ApplyMove(Level level, State state, char move) {
+
+        int direction = level.Direction(move);
+        int player = state.Player + direction;
+
+        if (char.IsUpper(move)) {
+            return state.PushBox(level, player, player + direction);
+        }
+
+        return state.MovePlayer(level, player);
+    }
+
+    // Expands macro actions into one node per player step, as expected by the renderer
+    static List<Node> ExpandSolutionPath(Level level, List<Node> solutionPath) {
+
+        List<Node> stepPath = new List<Node> { solutionPath[0] };
+        Node previous = solutionPath[0];
+
+        for (int i = 1; i < solutionPath.Count; i++) {
+            foreach (char move in solutionPath[i].Action) {
+                State stepState = ApplyMove(level, previous.StateNode, move);
+                Node stepNode = new Node(stepPath.Count, stepState, previous, move.ToString(), previous.Depth + 1, previous.Cost + 1, 0.00f, 0.00f);
+                stepPath.Add(stepNode);
+                previous = stepNode;
+            }
         }
 
-        return allBoxesOnTarget;
+        return stepPath;
     }
 
-    // Returns the list of nodes from the root
-    static List<Node> SearchAlgorithm(Level level, State state, string depth, string strategy) {
+    // Returns the solution path of a LURD string, one node per player step
+    static List<Node> MovesPath(Level level, Node rootNode, string moves) {
+
+        List<Node> stepPath = new List<Node> { rootNode };
+        Node previous = rootNode;
+
+        foreach (char move in moves) {
+            State stepState = ApplyMove(level, previous.StateNode, move);
+            Node stepNode = new Node(stepPath.Count, stepState, previous, move.ToString(), previous.Depth + 1, previous.Cost + 1, 0.00f, 0.00f);
+            stepPath.Add(stepNode);
+            previous = stepNode;
+        }
+
+        return stepPath;
+    }
+
+    // Returns a verified solution with no more moves than the given one (LURD).
+    // Walks between pushes are replaced by shortest walks, then every window of a few consecutive pushes
+    // is searched again for a cheaper way to reach the same boxes, which also reorders pushes inside the window.
+    internal static string OptimizeSolution(Level level, State state, string moves) {
+
+        List<(int, char)> pushes = SolutionPushes(level, state, moves);
+
+        // States before every push and moves of every push (walk + push)
+        List<State> states = new List<State> { state };
+        List<int> pushMoves = new List<int>();
+        InsertPushCosts(level, pushes, states, pushMoves, 0, pushes.Count);
+
+        bool improved = true;
+
+        while (improved) {
+            improved = false;
+
+            for (int i = 0; i + OPTIMIZER_WINDOW <= pushes.Count; i++) {
+                int end = i + OPTIMIZER_WINDOW;
+
+                // The walk to the push after the window depends on where the window leaves the player
+                int nextStanding = end < pushes.Count ? pushes[end].Item1 - level.Direction(pushes[end].Item2) : -1;
+                int windowMoves = pushMoves.Skip(i).Take(OPTIMIZER_WINDOW).Sum() + (end < pushes.Count ? pushMoves[end] - 1 : 0);
+
+                string? window = SearchPushWindow(level, states[i], states[end].Boxes, nextStanding, windowMoves);
+
+                if (window == null) {
+                    continue;
+                }
+
+                List<(int, char)> windowPushes = SolutionPushes(level, states[i], window);
+
+                pushes.RemoveRange(i, OPTIMIZER_WINDOW);
+                pushes.InsertRange(i, windowPushes);
+                states.RemoveRange(i + 1, OPTIMIZER_WINDOW);
+                pushMoves.RemoveRange(i, OPTIMIZER_WINDOW);
+                InsertPushCosts(level, pushes, states, pushMoves, i, windowPushes.Count);
+
+                // The window leaves the same boxes, only the walk to the next push changes
+                int next = i + windowPushes.Count;
+
+                if (next < pushes.Count) {
+                    (int box, char push) = pushes[next];
+                    pushMoves[next] = WalkDistance(level, states[next], box - level.Direction(push)) + 1;
+                }
+
+                improved = true;
+            }
+        }
+
+        string optimized = WalkPushes(level, state, pushes);
+
+        // Keep the original solution if the optimized one doesn't replay
+        Verifier verifier = new Verifier(level, state, optimized);
+
+        if (!verifier.Solved || optimized.Length > moves.Length) {
+            return moves;
+        }
+
+        return optimized;
+    }
+
+    // Returns the pushes of a LURD solution as the box cell and the push move
+    static List<(int, char)> SolutionPushes(Level level, State state, string moves) {
+
+        List<(int, char)> pushes = new List<(int, char)>();
+
+        foreach (char move in moves) {
+            if (char.IsUpper(move)) {
+                pushes.Add((state.Player + level.Direction(move), move));
+            }
+            state = ApplyMove(level, state, move);
+        }
+
+        return pushes;
+    }
+
+    // Inserts the states after a run of pushes and the moves of those pushes (walk + push),
+    // starting from the state before the first push of the run
+    static void InsertPushCosts(Level level, List<(int, char)> pushes, List<State> states, List<int> pushMoves, int from, int count) {
+
+        for (int k = from; k < from + count; k++) {
+            (int box, char push) = pushes[k];
+            int direction = level.Direction(push);
+            pushMoves.Insert(k, WalkDistance(level, states[k], box - direction) + 1);
+            states.Insert(k + 1, states[k].PushBox(level, box, box + direction));
+        }
+    }
+
+    // Returns the LURD solution of a list of pushes, walking the shortest path to every push
+    static string WalkPushes(Level level, State state, List<(int, char)> pushes) {
+
+        StringBuilder sb = new StringBuilder();
+
+        foreach (var (box, push) in pushes) {
+            int direction = level.Direction(push);
+            sb.Append(WalkPath(level, level.ReachableRegion(state), state.Player, box - direction));
+            sb.Append(push);
+            state = state.PushBox(level, box, box + direction);
+        }
+
+        return sb.ToString();
+    }
+
+    // Returns the length of the shortest walk from the player to a cell, or INFINITE_DISTANCE if it can't be reached
+    static int WalkDistance(Level level, State state, int cell) {
+
+        int[] reachable = level.ReachableRegion(state);
+
+        if (reachable[cell] == -1) {
+            return Level.INFINITE_DISTANCE;
+        }
+
+        return WalkPath(level, reachable, state.Player, cell).Length;
+    }
+
+    // Returns the cheapest LURD moves (uniform cost over push successors, at most OPTIMIZER_WINDOW pushes) from a state
+    // to the given boxes, counting the walk to the next push, or null if there is none cheaper than maxMoves
+    static string? SearchPushWindow(Level level, State state, short[] goalBoxes, int nextStanding, int maxMoves) {
 
         var comparator = Comparer<Node>.Create((x, y) => {
             int comparatorValue = x.ValueNode.CompareTo(y.ValueNode);
@@ -261,20 +808,128 @@ class Program {
         });
 
         var frontier = new PriorityQueue<Node>(comparator);
-        HashSet<string> visited = new HashSet<string>();
+        VisitedSet visited = new VisitedSet();
+        int nodesCount = 0;
+        int bestMoves = maxMoves;
+        Node? bestNode = null;
+
+        frontier.Add(new Node(0, state, null, "NOTHING", 0, 0.00f, 0.00f, 0.00f));
+
+        while (frontier.Count != 0 && visited.Count < OPTIMIZER_STATES) {
+            Node node = frontier.Poll();
+
+            // Every remaining node costs at least as much as the best window found
+            if (node.Cost >= bestMoves) {
+                break;
+            }
+
+            if (visited.Contains(node.StateNode)) {
+                continue;
+            }
+            visited.Add(node.StateNode);
+
+            if (node.StateNode.Boxes.SequenceEqual(goalBoxes)) {
+                int windowMoves = (int)node.Cost + (nextStanding != -1 ? WalkDistance(level, node.StateNode, nextStanding) : 0);
+
+                if (windowMoves < bestMoves) {
+                    bestMoves = windowMoves;
+                    bestNode = node;
+                }
+                continue;
+            }
+
+            if (node.Depth >= OPTIMIZER_WINDOW) {
+                continue;
+            }
+
+            foreach (var nodeSuc in PushSuccessorFunction(level, node.StateNode, false)) {
+                nodesCount++;
+                Node childNode = new Node(nodesCount, nodeSuc.State, node, nodeSuc.Action, node.Depth + 1, node.Cost + nodeSuc.Cost, 0.00f, node.Cost + nodeSuc.Cost);
+                frontier.Add(childNode);
+            }
+        }
+
+        if (bestNode == null) {
+            return null;
+        }
+
+        List<string> actions = new List<string>();
+        Node current = bestNode;
+
+        while (current.ParentNode != null) {
+            actions.Add(current.Action);
+            current = current.ParentNode;
+        }
+        actions.Reverse();
+
+        return string.Concat(actions);
+    }
+
+    // Returns if the objective function (all boxes are on targets) is achieved.
+    // Levels may have more targets than boxes: any subset of targets holding every box is a goal.
+    internal static bool ObjectiveFunction(Level level, State state) {
+
+        foreach (var box in state.Boxes) {
+            if (!level.IsTarget(box)) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    // Runs the search selected by the strategy, timing it and collecting its statistics
+    internal static SearchResult Search(Level level, State state, Param param) {
+
+        SearchStatistics statistics = new SearchStatistics();
+        SearchResult result;
+
+        statistics.Start();
+
+        // Iterative deepening strategies keep only the current path instead of a frontier
+        if (param.Strategy == "IDDFS" || param.Strategy == "IDA*") {
+            result = IterativeDeepeningSearch(level, state, param, statistics);
+        }
+        else if (param.Strategy == "BIDIR") {
+            result = BidirectionalSearch(level, state, param, statistics);
+        }
+        else {
+            result = SearchAlgorithm(level, state, param, statistics);
+        }
+
+        statistics.Stop();
+
+        return result;
+    }
+
+    // Returns the result of the search, solved results hold the list of nodes from the root
+    static SearchResult SearchAlgorithm(Level level, State state, Param param, SearchStatistics statistics) {
+
+        string strategy = param.Strategy;
+
+        var comparator = Comparer<Node>.Create((x, y) => {
+            int comparatorValue = x.ValueNode.CompareTo(y.ValueNode);
+            return comparatorValue != 0 ? comparatorValue : x.IdNode.CompareTo(y.IdNode);
+        });
+
+        var frontier = new PriorityQueue<Node>(comparator);
+        VisitedSet visited = new VisitedSet();
         bool solution = false;
 
-        int maxDepth = int.Parse(depth);
-        int totalNodes = 0;
+        int maxDepth = int.Parse(param.Depth);
 
-        Node rootNode = new Node(totalNodes, state, null, "NOTHING", 0, 0.00f, 0.00f, 0.00f);
-        rootNode.AssignValue(strategy, level);
+        Node rootNode = CreateRootNode(level, state, param);
         frontier.Add(rootNode); // insert root node in frontier
 
         Node node = rootNode;
 
         // Check if frontier is not empty and is not solution
         while (frontier.Count != 0 && !solution) {
+            // Stop when the time or node limit runs out
+            if (BudgetExceeded(param, statistics)) {
+                break;
+            }
+
             // is solution
             // extract node from frontier
             node = frontier.Poll();
@@ -284,40 +939,345 @@ class Program {
                 solution = true;
             }
             else {
-                if (node.Depth < maxDepth && !visited.Contains(node.StateNode.Id)) {
+                if (visited.Contains(node.StateNode)) {
+                    statistics.DuplicatesSkipped++;
+                }
+                else if (node.Depth >= maxDepth) {
+                    // depth bound reached, a new state is not expanded (it stays unvisited for shallower paths)
+                    statistics.DepthPruned = true;
+                }
+                else {
                     // correct depth and state not visited
-                    visited.Add(node.StateNode.Id);
+                    visited.Add(node.StateNode);
 
-                    List<Successor> nodeSuccessors = SuccessorFunction(level, node.StateNode);
                     // expand node
-                    foreach (var nodeSuc in nodeSuccessors) {
-                        totalNodes++;
-                        Node childNode = new Node(totalNodes, nodeSuc.State, node, nodeSuc.Action, node.Depth + 1, node.Cost + 1, 0.00f, 0.00f);
-                        childNode.AssignValue(strategy, level);
+                    foreach (var childNode in ExpandNode(level, node, param, statistics)) {
                         frontier.Add(childNode);
                     }
+                    statistics.RecordFrontier(frontier.Count);
                 }
             }
         }
 
         // Check if a solution was found
         if (solution) {
-            List<Node> solutionPath = new List<Node>();
+            return new SearchResult(BuildSolutionPath(level, node, param), statistics, maxDepth);
+        }
 
-            // Traverse from the current node to the root
-            while (node != null) {
-                solutionPath.Add(node);
-                node = node.ParentNode;
+        else {
+            return new SearchResult(statistics, maxDepth);
+        }
+    }
+
+    // Returns the result of a memory-bounded iterative deepening search.
+    // IDDFS bounds the depth and IDA* bounds cost + heuristic, raising the bound after every failed iteration.
+    static SearchResult IterativeDeepeningSearch(Level level, State state, Param param, SearchStatistics statistics) {
+
+        int maxDepth = int.Parse(param.Depth);
+
+        Node rootNode = CreateRootNode(level, state, param);
+        double bound = rootNode.ValueNode;
+
+        while (true) {
+            double nextBound = double.PositiveInfinity;
+            Node? goalNode = BoundedDepthFirstSearch(level, rootNode, bound, ref nextBound, maxDepth, param, statistics);
+
+            if (goalNode != null) {
+                return new SearchResult(BuildSolutionPath(level, goalNode, param), statistics, maxDepth);
             }
-            solutionPath.Reverse();
 
-            return solutionPath;
+            // Nothing was cut by the bound, raising it won't reach new nodes
+            if (double.IsPositiveInfinity(nextBound) || BudgetExceeded(param, statistics)) {
+                break;
+            }
+
+            bound = nextBound;
         }
 
-        else {
-            Console.WriteLine("There is no solution.");
+        return new SearchResult(statistics, maxDepth);
+    }
+
+    // Returns the result of a forward search (pushes) meeting a backward search (pulls) started from every goal state.
+    // Both searches grow one layer at a time, the smaller frontier first.
+    static SearchResult BidirectionalSearch(Level level, State state, Param param, SearchStatistics statistics) {
+
+        int maxDepth = int.Parse(param.Depth);
+
+        Node rootNode = CreateRootNode(level, state, param);
+
+        if (ObjectiveFunction(level, rootNode.StateNode)) {
+            return new SearchResult(BuildSolutionPath(level, rootNode, param), statistics, maxDepth);
+        }
+
+        // Nodes of each search grouped by state id to find where they meet
+        Dictionary<ulong, List<Node>> forwardNodes = new Dictionary<ulong, List<Node>>();
+        Dictionary<ulong, List<Node>> backwardNodes = new Dictionary<ulong, List<Node>>();
+        Queue<Node> forwardFrontier = new Queue<Node>();
+        Queue<Node> backwardFrontier = new Queue<Node>();
+
+        AddSearchNode(forwardNodes, rootNode);
+        forwardFrontier.Enqueue(rootNode);
+
+        // Every way of choosing the box targets is a goal, surplus targets grow them combinatorially
+        long goalSubsets = GoalSubsetsCount(level.Targets.Length, state.Boxes.Length);
+
+        if (goalSubsets > MAX_GOAL_SUBSETS) {
+            throw new InvalidOperationException("BIDIR would start from " + goalSubsets + " target subsets (at most " + MAX_GOAL_SUBSETS + "), use another strategy.");
+        }
+
+        foreach (var goalState in GoalStates(level, state)) {
+            Node goalNode = new Node(0, goalState, null, "NOTHING", 0, 0.00f, 0.00f, 0.00f);
+            AddSearchNode(backwardNodes, goalNode);
+            backwardFrontier.Enqueue(goalNode);
+        }
+
+        int forwardDepth = 0;
+        int backwardDepth = 0;
+
+        while (forwardFrontier.Count != 0 && backwardFrontier.Count != 0 && forwardDepth + backwardDepth < maxDepth) {
+            bool forward = forwardFrontier.Count <= backwardFrontier.Count;
+            Queue<Node> frontier = forward ? forwardFrontier : backwardFrontier;
+            Queue<Node> nextFrontier = new Queue<Node>();
+
+            // Expand a whole layer
+            while (frontier.Count != 0) {
+                if (BudgetExceeded(param, statistics)) {
+                    return new SearchResult(statistics, maxDepth);
+                }
+
+                Node node = frontier.Dequeue();
+                List<Node> childNodes = forward ? ExpandNode(level, node, param, statistics) : ExpandPullNode(level, node, statistics);
+
+                foreach (var childNode in childNodes) {
+                    if (!AddSearchNode(forward ? forwardNodes : backwardNodes, childNode)) {
+                        statistics.DuplicatesSkipped++;
+                        continue;
+                    }
+
+                    // Check if the other search already reached this state
+                    Node? meetingNode = FindSearchNode(forward ? backwardNodes : forwardNodes, childNode.StateNode);
+
+                    if (meetingNode != null) {
+                        List<Node> solutionPath = forward
+                            ? StitchSolutionPath(level, childNode, meetingNode, param)
+                            : StitchSolutionPath(level, meetingNode, childNode, param);
+                        return new SearchResult(solutionPath, statistics, maxDepth);
+                    }
+
+                    nextFrontier.Enqueue(childNode);
+                }
+
+                statistics.RecordFrontier(frontier.Count + nextFrontier.Count + (forward ? backwardFrontier.Count : forwardFrontier.Count));
+            }
+
+            if (forward) {
+                forwardFrontier = nextFrontier;
+                forwardDepth++;
+            }
+            else {
+                backwardFrontier = nextFrontier;
+                backwardDepth++;
+            }
+        }
+
+        // Both searches still had nodes to expand when the depth bound stopped them,
+        // otherwise one of them ran out of states and the level is unsolvable
+        if (forwardFrontier.Count != 0 && backwardFrontier.Count != 0) {
+            statistics.DepthPruned = true;
+        }
+
+        return new SearchResult(statistics, maxDepth);
+    }
+
+    // Returns the child nodes of a backward search node
+    static List<Node> ExpandPullNode(Level level, Node node, SearchStatistics statistics) {
+
+        List<Node> childNodes = new List<Node>();
+        statistics.RecordExpansion(node.Depth);
+
+        foreach (var nodeSuc in PullSuccessorFunction(level, node.StateNode)) {
+            statistics.TotalNodes++;
+            childNodes.Add(new Node(statistics.TotalNodes, nodeSuc.State, node, nodeSuc.Action, node.Depth + 1, node.Cost + nodeSuc.Cost, 0.00f, 0.00f));
+        }
+
+        return childNodes;
+    }
+
+    // Adds a node to the nodes of a search, returns false if its state was already reached
+    static bool AddSearchNode(Dictionary<ulong, List<Node>> searchNodes, Node node) {
+
+        if (FindSearchNode(searchNodes, node.StateNode) != null) {
+            return false;
+        }
+
+        if (!searchNodes.TryGetValue(node.StateNode.Id, out List<Node>? sameId)) {
+            sameId = new List<Node>();
+            searchNodes[node.StateNode.Id] = sameId;
+        }
+
+        sameId.Add(node);
+        return true;
+    }
+
+    // Returns the node of a search that reached the state, or null
+    static Node? FindSearchNode(Dictionary<ulong, List<Node>> searchNodes, State state) {
+
+        if (!searchNodes.TryGetValue(state.Id, out List<Node>? sameId)) {
             return null;
         }
+
+        return sameId.FirstOrDefault(node => node.StateNode.SameAs(state));
+    }
+
+    // Returns the path from the root to the forward node followed by the backward node moves back to its goal state
+    static List<Node> StitchSolutionPath(Level level, Node forwardNode, Node backwardNode, Param param) {
+
+        List<Node> solutionPath = BuildSolutionPath(level, forwardNode, param);
+        Node previous = solutionPath[solutionPath.Count - 1];
+        Node? current = backwardNode;
+
+        // Every backward node action is the forward move to its parent state
+        while (current.ParentNode != null) {
+            State stepState = ApplyMove(level, previous.StateNode, current.Action[0]);
+            Node stepNode = new Node(solutionPath.Count, stepState, previous, current.Action, previous.Depth + 1, previous.Cost + 1, 0.00f, 0.00f);
+            solutionPath.Add(stepNode);
+            previous = stepNode;
+            current = current.ParentNode;
+        }
+
+        return solutionPath;
+    }
+
+    // Depth-first search of the nodes whose value is within the bound, keeping only the current path in memory.
+    // The smallest value above the bound is stored in nextBound for the next iteration.
+    static Node? BoundedDepthFirstSearch(Level level, Node node, double bound, ref double nextBound, int maxDepth, Param param, SearchStatistics statistics) {
+
+        if (node.ValueNode > bound) {
+            nextBound = Math.Min(nextBound, node.ValueNode);
+            return null;
+        }
+
+        if (BudgetExceeded(param, statistics)) {
+            return null;
+        }
+
+        if (ObjectiveFunction(level, node.StateNode)) {
+            return node;
+        }
+
+        if (node.Depth >= maxDepth) {
+            statistics.DepthPruned = true;
+            return null;
+        }
+
+        // Most promising children first
+        foreach (var childNode in ExpandNode(level, node, param, statistics).OrderBy(child => child.ValueNode)) {
+            if (IsOnPath(childNode)) {
+                statistics.DuplicatesSkipped++;
+                continue;
+            }
+
+            Node? goalNode = BoundedDepthFirstSearch(level, childNode, bound, ref nextBound, maxDepth, param, statistics);
+
+            if (goalNode != null) {
+                return goalNode;
+            }
+        }
+
+        return null;
+    }
+
+    // Returns if the node state already appears in one of its ancestors
+    static bool IsOnPath(Node node) {
+
+        Node? ancestor = node.ParentNode;
+
+        while (ancestor != null) {
+            if (ancestor.StateNode.Id == node.StateNode.Id && ancestor.StateNode.SameAs(node.StateNode)) {
+                return true;
+            }
+            ancestor = ancestor.ParentNode;
+        }
+
+        return false;
+    }
+
+    // Returns the root node of the search
+    static Node CreateRootNode(Level level, State state, Param param) {
+
+        // Identify states by the player region instead of the exact player cell
+        if (param.NormalizePlayer) {
+            state.NormalizeId(level);
+        }
+
+        Node rootNode = new Node(0, state, null, "NOTHING", 0, 0.00f, 0.00f, 0.00f);
+        rootNode.AssignValue(param, level);
+
+        return rootNode;
+    }
+
+    // Returns the child nodes of a node, discarding pushes into freeze deadlocks
+    static List<Node> ExpandNode(Level level, Node node, Param param, SearchStatistics statistics) {
+
+        List<Node> childNodes = new List<Node>();
+        statistics.RecordExpansion(node.Depth);
+        statistics.RecordBestNode(node, RemainingDistance(level, node.StateNode));
+
+        // Step-level successors move the player one cell, push-level successors walk and push a box
+        List<Successor> nodeSuccessors = param.PushLevel
+            ? PushSuccessorFunction(level, node.StateNode, param.Tunnels)
+            : SuccessorFunction(level, node.StateNode, param.Tunnels);
+
+        foreach (var nodeSuc in nodeSuccessors) {
+            // Discard pushes that leave boxes frozen outside of targets
+            if (IsPush(nodeSuc.Action)) {
+                int pushedBox = nodeSuc.State.Player + level.Direction(nodeSuc.Action[nodeSuc.Action.Length - 1]);
+
+                if (Deadlock.IsFreezeDeadlock(level, nodeSuc.State, pushedBox)) {
+                    statistics.FrozenDeadlocks++;
+                    continue;
+                }
+            }
+
+            if (param.NormalizePlayer) {
+                nodeSuc.State.NormalizeId(level);
+            }
+
+            statistics.TotalNodes++;
+            // Successor costs count moves, the objective decides how moves and pushes are weighed
+            double cost = Node.ObjectiveCost(param.Objective, nodeSuc.Cost, PushesCount(nodeSuc.Action));
+            Node childNode = new Node(statistics.TotalNodes, nodeSuc.State, node, nodeSuc.Action, node.Depth + 1, node.Cost + cost, 0.00f, 0.00f);
+            childNode.AssignValue(param, level);
+            childNodes.Add(childNode);
+        }
+
+        return childNodes;
+    }
+
+    // Returns the list of nodes from the root to the solution node
+    static List<Node> BuildSolutionPath(Level level, Node node, Param param) {
+
+        List<Node> solutionPath = new List<Node>();
+        Node? current = node;
+
+        // Traverse from the current node to the root
+        while (current != null) {
+            solutionPath.Add(current);
+            current = current.ParentNode;
+        }
+        solutionPath.Reverse();
+
+        // Push-level nodes hold a walk and a push, tunnel macros several pushes, the renderer needs every step
+        if (param.PushLevel || param.Tunnels) {
+            solutionPath = ExpandSolutionPath(level, solutionPath);
+        }
+
+        return solutionPath;
+    }
+
+    // Returns if the successor action pushes a box (upper-case moves, macro actions end with their push)
+    static bool IsPush(string action) {
+        return action.Length > 0 && char.IsUpper(action[action.Length - 1]);
     }
 
     // Prints a jagged array for debugging purposes
@@ -123,19 +123,15 @@ public class Renderer {
             // Lerp
             if (currentNodeIndex < nodeSolution.Count - 1) {
 
-                // Set start and end positions for the current node transition
-                startPlayerPos = (nodeSolution[currentNodeIndex].StateNode.Player.Item1,
-                                  nodeSolution[currentNodeIndex].StateNode.Player.Item2);
-                endPlayerPos = (nodeSolution[currentNodeIndex + 1].StateNode.Player.Item1,
-                                nodeSolution[currentNodeIndex + 1].StateNode.Player.Item2);
+                State startState = nodeSolution[currentNodeIndex].StateNode;
+                State endState = nodeSolution[currentNodeIndex + 1].StateNode;
 
-                startBoxesPos = nodeSolution[currentNodeIndex].StateNode.Boxes
-                    .Select(row => row.Select(col => (float)col).ToArray())
-                    .ToArray();
+                // Set start and end positions for the current node transition
+                startPlayerPos = (level.Row(startState.Player), level.Col(startState.Player));
+                endPlayerPos = (level.Row(endState.Player), level.Col(endState.Player));
 
-                endBoxesPos = nodeSolution[currentNodeIndex + 1].StateNode.Boxes
-                    .Select(row => row.Select(col => (float)col).ToArray())
-                    .ToArray();
+                startBoxesPos = CellsToCoords(startState.Boxes);
+                endBoxesPos = MatchBoxes(startState, endState);
 
                 // Update interpolation progress
                 if (timeElapsed < duration) {
@@ -200,6 +196,22 @@ public class Renderer {
         return (x, y);
     }
 
+    // Converts box cells into (row, col) coordinates
+    private float[][] CellsToCoords(short[] boxes) {
+        return boxes.Select(box => new float[] { level.Row(box), level.Col(box) }).ToArray();
+    }
+
+    // Returns the end coordinates of boxes in the same order as the start state.
+    // Box cells are kept sorted, so a pushed box may change its index between states.
+    private float[][] MatchBoxes(State startState, State endState) {
+        short[] movedTo = endState.Boxes.Where(box => !startState.HasBox(box)).ToArray();
+
+        return startState.Boxes
+            .Select(box => endState.HasBox(box) || movedTo.Length == 0 ? box : movedTo[0])
+            .Select(box => new float[] { level.Row(box), level.Col(box) })
+            .ToArray();
+    }
+
     private static float[][] LerpBoxes(float[][] startPos, float[][] endPos, float amount) {
         // Initialize a new float array for the interpolated positions
         float[][] boxMoves = new float[startPos.Length][];
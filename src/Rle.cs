@@ -0,0 +1,115 @@
+using System.Text;
+
+namespace Sokoban;
+
+public static class Rle {
+
+    // Returns a run-length encoded level ("4#|#@$.#") as rows separated by '\n'. '-' and '_' are floors.
+    public static string DecodeLevel(string rle) {
+        return Decode(rle)
+            .Replace('|', '\n')
+            .Replace('-', ' ')
+            .Replace('_', ' ');
+    }
+
+    // Returns a level (rows separated by '\n') run-length encoded with '|' row separators and '-' floors
+    public static string EncodeLevel(string level) {
+
+        List<string> rows = new List<string>();
+
+        foreach (var row in level.Split('\n')) {
+            rows.Add(Encode(row.TrimEnd().Replace(' ', '-')));
+        }
+
+        return string.Join("|", rows);
+    }
+
+    // Returns if a level string is run-length encoded (row separators or run counts)
+    public static bool IsEncodedLevel(string level) {
+        return level.Contains('|') || level.Any(char.IsDigit);
+    }
+
+    // Expands the run counts of a string ("3r2Ul" -> "rrrUUl"), parentheses repeat a whole group ("2(rL)")
+    public static string Decode(string rle) {
+
+        int index = 0;
+        string decoded = DecodeGroup(rle, ref index);
+
+        if (index < rle.Length) {
+            throw new FormatException("Unbalanced parentheses in: " + rle);
+        }
+
+        return decoded;
+    }
+
+    // Compresses repeated characters of a string with run counts ("rrrUUl" -> "3r2Ul")
+    public static string Encode(string text) {
+
+        StringBuilder sb = new StringBuilder();
+        int i = 0;
+
+        while (i < text.Length) {
+            int run = 1;
+
+            while (i + run < text.Length && text[i + run] == text[i]) {
+                run++;
+            }
+
+            if (run > 1) {
+                sb.Append(run);
+            }
+            sb.Append(text[i]);
+            i += run;
+        }
+
+        return sb.ToString();
+    }
+
+    // Decodes until the end of the string or the closing parenthesis of the current group
+    private static string DecodeGroup(string rle, ref int index) {
+
+        StringBuilder sb = new StringBuilder();
+
+        while (index < rle.Length && rle[index] != ')') {
+            int count = 0;
+            bool hasCount = false;
+
+            while (index < rle.Length && char.IsDigit(rle[index])) {
+                count = count * 10 + (rle[index] - '0');
+                hasCount = true;
+                index++;
+            }
+
+            if (index >= rle.Length) {
+                throw new FormatException("Run count without a character in: " + rle);
+            }
+
+            if (hasCount && count == 0) {
+                throw new FormatException("Zero run count in: " + rle);
+            }
+
+            string item;
+
+            if (rle[index] == '(') {
+                index++;
+                item = DecodeGroup(rle, ref index);
+
+                if (index >= rle.Length) {
+                    throw new FormatException("Unbalanced parentheses in: " + rle);
+                }
+                index++;    // Skip ')'
+            }
+            else if (rle[index] == ')') {
+                throw new FormatException("Run count without a character in: " + rle);
+            }
+            else {
+                item = rle[index].ToString();
+                index++;
+            }
+
+            sb.Insert(sb.Length, item, hasCount ? count : 1);
+        }
+
+        return sb.ToString();
+    }
+}
@@ -0,0 +1,62 @@
+namespace Sokoban;
+
+// Outcomes of a search
+public enum SearchOutcome {
+    Solved,             // A solution path was found
+    Unsolvable,         // Every reachable state was explored without a solution
+    Inconclusive,       // No solution, but nodes were pruned by the depth bound
+    LimitReached,       // A time, node or memory budget stopped the search
+}
+
+public class SearchResult {
+    private SearchOutcome outcome;              // Outcome of the search
+    private List<Node>? solutionPath;           // Nodes from the root to the goal (null if not solved)
+    private SearchStatistics statistics;        // Statistics of the search
+    private int depthBound;                     // Depth bound of the search
+
+    // SearchResult constructor 1 (solved)
+    public SearchResult(List<Node> solutionPath, SearchStatistics statistics, int depthBound) {
+        outcome = SearchOutcome.Solved;
+        this.solutionPath = solutionPath;
+        this.statistics = statistics;
+        this.depthBound = depthBound;
+    }
+
+    // SearchResult constructor 2 (not solved, the statistics tell why the search stopped)
+    public SearchResult(SearchStatistics statistics, int depthBound) {
+        if (statistics.ExceededBudget != "") {
+            outcome = SearchOutcome.LimitReached;
+        }
+        else if (statistics.DepthPruned) {
+            outcome = SearchOutcome.Inconclusive;
+        }
+        else {
+            outcome = SearchOutcome.Unsolvable;
+        }
+
+        this.statistics = statistics;
+        this.depthBound = depthBound;
+    }
+
+    // Properties
+    public SearchOutcome Outcome => outcome;            // Outcome Getter
+    public List<Node>? SolutionPath => solutionPath;    // Solution path Getter
+    public SearchStatistics Statistics => statistics;   // Statistics Getter
+    public int DepthBound => depthBound;                // Depth bound Getter
+    public bool Solved => solutionPath != null;         // Solved Getter
+    public Node? BestNode => statistics.BestNode;       // Best partial node Getter (closest to the goal)
+
+    // Returns a short description of the outcome
+    public string Describe() {
+        switch (outcome) {
+            case SearchOutcome.Solved:
+                return "Solved";
+            case SearchOutcome.Unsolvable:
+                return "There is no solution.";
+            case SearchOutcome.Inconclusive:
+                return "Inconclusive at depth " + depthBound + ": nodes were pruned by the depth bound.";
+            default:
+                return "Limit reached (" + statistics.ExceededBudget + "), no solution found.";
+        }
+    }
+}
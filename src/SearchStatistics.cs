@@ -0,0 +1,129 @@
+using System.Diagnostics;
+using System.Text.Json;
+
+namespace Sokoban;
+
+public class SearchStatistics {
+
+    // Expansions between two samples of the managed heap size
+    private const int MEMORY_SAMPLE_INTERVAL = 1024;
+
+    private int totalNodes;
+    private int nodesExpanded;
+    private int duplicatesSkipped;
+    private int maxFrontier;
+    private int maxDepth;
+    private int frozenDeadlocks;
+    private long peakMemory;
+    private bool depthPruned;
+    private string exceededBudget = "";
+    private Node? bestNode;
+    private int bestDistance = int.MaxValue;
+    private Stopwatch stopwatch = new Stopwatch();
+
+    // TotalNodes Getter and Setter (nodes generated)
+    public int TotalNodes {
+        get => totalNodes;
+        set => totalNodes = value;
+    }
+
+    // DuplicatesSkipped Getter and Setter (nodes discarded because their state was already reached)
+    public int DuplicatesSkipped {
+        get => duplicatesSkipped;
+        set => duplicatesSkipped = value;
+    }
+
+    // FrozenDeadlocks Getter and Setter
+    public int FrozenDeadlocks {
+        get => frozenDeadlocks;
+        set => frozenDeadlocks = value;
+    }
+
+    // DepthPruned Getter and Setter (a node was not expanded because of the depth bound)
+    public bool DepthPruned {
+        get => depthPruned;
+        set => depthPruned = value;
+    }
+
+    // ExceededBudget Getter and Setter (time / nodes / memory budget that stopped the search, empty if none)
+    public string ExceededBudget {
+        get => exceededBudget;
+        set => exceededBudget = value;
+    }
+
+    // Properties
+    public int NodesExpanded => nodesExpanded;          // Nodes expanded Getter
+    public int MaxFrontier => maxFrontier;              // Largest frontier size Getter
+    public int MaxDepth => maxDepth;                    // Deepest expanded node Getter
+    public long PeakMemory => peakMemory;               // Managed heap high-water mark Getter (bytes)
+    public TimeSpan Elapsed => stopwatch.Elapsed;       // Wall-clock time Getter
+    public Node? BestNode => bestNode;                  // Expanded node closest to the goal Getter
+
+    // Starts the wall-clock timer
+    public void Start() {
+        peakMemory = Math.Max(peakMemory, GC.GetTotalMemory(false));
+        stopwatch.Start();
+    }
+
+    // Stops the wall-clock timer
+    public void Stop() {
+        stopwatch.Stop();
+        peakMemory = Math.Max(peakMemory, GC.GetTotalMemory(false));
+    }
+
+    // Records the expansion of a node, sampling the heap size every few expansions
+    public void RecordExpansion(int depth) {
+        nodesExpanded++;
+        maxDepth = Math.Max(maxDepth, depth);
+
+        if (nodesExpanded % MEMORY_SAMPLE_INTERVAL == 0) {
+            peakMemory = Math.Max(peakMemory, GC.GetTotalMemory(false));
+        }
+    }
+
+    // Records an expanded node if its boxes are closer to the targets than the best one so far
+    public void RecordBestNode(Node node, int distance) {
+        if (distance < bestDistance) {
+            bestDistance = distance;
+            bestNode = node;
+        }
+    }
+
+    // Records the current frontier size
+    public void RecordFrontier(int size) {
+        maxFrontier = Math.Max(maxFrontier, size);
+    }
+
+    // Prints the search statistics
+    public void PrintStatistics() {
+        Console.WriteLine("Nodes generated: " + totalNodes);
+        Console.WriteLine("Nodes expanded: " + nodesExpanded);
+        Console.WriteLine("Duplicates skipped: " + duplicatesSkipped);
+        Console.WriteLine("States cut by freeze deadlocks: " + frozenDeadlocks);
+        Console.WriteLine("Max frontier size: " + maxFrontier);
+        Console.WriteLine("Max depth: " + maxDepth);
+        Console.WriteLine("Time: " + stopwatch.ElapsedMilliseconds + " ms");
+        Console.WriteLine("Peak memory: " + peakMemory / (1024 * 1024) + " MB");
+    }
+
+    // Prints the search statistics as a JSON object
+    public void PrintJson() {
+        Console.WriteLine(ToJson());
+    }
+
+    // Returns the search statistics as a JSON object
+    public string ToJson() {
+        return JsonSerializer.Serialize(new Dictionary<string, object> {
+            ["nodes_generated"] = totalNodes,
+            ["nodes_expanded"] = nodesExpanded,
+            ["duplicates_skipped"] = duplicatesSkipped,
+            ["frozen_deadlocks"] = frozenDeadlocks,
+            ["max_frontier"] = maxFrontier,
+            ["max_depth"] = maxDepth,
+            ["time_ms"] = stopwatch.ElapsedMilliseconds,
+            ["peak_memory_bytes"] = peakMemory,
+            ["depth_pruned"] = depthPruned,
+            ["exceeded_budget"] = exceededBudget,
+        });
+    }
+}
@@ -0,0 +1,77 @@
+using System.Xml.Linq;
+
+namespace Sokoban;
+
+public static class Slc {
+
+    // Returns every level of a SLC file (Sokoban Level Collection XML), titled by their Id
+    public static List<LevelFile> Load(string path) {
+
+        XDocument document = XDocument.Load(path);
+        List<LevelFile> levels = new List<LevelFile>();
+
+        foreach (var levelElement in Children(document.Root, "LevelCollection").SelectMany(collection => Children(collection, "Level"))) {
+            string[] rows = Children(levelElement, "L")
+                .Select(row => row.Value.Replace('-', ' ').Replace('_', ' '))
+                .ToArray();
+
+            LevelFile level = new LevelFile(string.Join("\n", rows), (string?)levelElement.Attribute("Id") ?? "");
+            level.Author = (string?)levelElement.Attribute("Copyright") ?? "";
+            level.Comment = Children(levelElement, "Comment").Select(comment => comment.Value.Trim()).FirstOrDefault() ?? "";
+            level.Solution = Children(levelElement, "Solution").Select(solution => solution.Value.Trim()).FirstOrDefault() ?? "";
+            levels.Add(level);
+        }
+
+        return levels;
+    }
+
+    // Writes the levels to a SLC file, solved levels include their solution (LURD).
+    // Authors are written as the Copyright attribute and comments as a Comment element.
+    public static void Save(string path, string title, List<LevelFile> levels) {
+
+        XElement collection = new XElement("LevelCollection");
+
+        foreach (var level in levels) {
+            string[] rows = level.Level.Split('\n').Select(row => row.TrimEnd()).ToArray();
+
+            XElement levelElement = new XElement("Level",
+                new XAttribute("Id", level.Title),
+                new XAttribute("Width", rows.Max(row => row.Length)),
+                new XAttribute("Height", rows.Length),
+                rows.Select(row => new XElement("L", row)));
+
+            if (level.Author != "") {
+                levelElement.Add(new XAttribute("Copyright", level.Author));
+            }
+
+            if (level.Comment != "") {
+                levelElement.Add(new XElement("Comment", level.Comment));
+            }
+
+            if (level.Solution != "") {
+                levelElement.Add(new XElement("Solution", level.Solution));
+            }
+
+            collection.Add(levelElement);
+        }
+
+        XDocument document = new XDocument(
+            new XDeclaration("1.0", "utf-8", null),
+            new XElement("SokobanLevels",
+                new XElement("Title", title),
+                new XElement("Description", ""),
+                collection));
+
+        document.Save(path);
+    }
+
+    // Returns the child elements with the given name, ignoring XML namespaces
+    private static IEnumerable<XElement> Children(XElement? element, string name) {
+
+        if (element == null) {
+            return Enumerable.Empty<XElement>();
+        }
+
+        return element.Elements().Where(child => child.Name.LocalName == name);
+    }
+}
@@ -1,49 +1,38 @@
 using System;
-using System.Security.Cryptography;
-using System.Text;
 
 namespace Sokoban;
 
 public class State {
-    private string id;
-    private (int, int) player;
-    private int[][] boxes;
+    private ulong id;               // Zobrist hash of boxes and player
+    private ulong boxesHash;        // Zobrist hash of boxes only
+    private int idPlayer;           // Player cell hashed into the id
+    private int player;             // Player cell
+    private short[] boxes;          // Box cells, sorted and shared between states (never modified)
 
     // State constructor 1
-    public State(string level) {
-        player = ObtainPlayerCoords(level);
-        boxes = ObtainBoxesCoords(level);
-        id = CalculateMD5Hash(player, boxes);
+    public State(string level, Level levelInfo) {
+        player = ObtainPlayerCell(level, levelInfo);
+        boxes = ObtainBoxesCells(level, levelInfo);
+        boxesHash = levelInfo.ZobristBoxesHash(boxes);
+        UpdateId(levelInfo, player);
     }
 
-    // State constructor 2
-    public State((int, int) player, int[][] boxes) {
+    // State constructor 2 (boxes hash is updated incrementally by the caller)
+    public State(int player, short[] boxes, ulong boxesHash, Level level) {
         this.player = player;
         this.boxes = boxes;
-        id = CalculateMD5Hash(player, boxes);
+        this.boxesHash = boxesHash;
+        UpdateId(level, player);
     }
 
     // Properties
-    // Player Getter and Setter
-    public (int, int) Player {
-        get => player;
-        set => player = value;
-    }
-
-    // Boxes Getter and Setter
-    public int[][] Boxes {
-        get => boxes;
-        set => boxes = value;
-    }
+    public int Player => player;                // Player Getter
+    public short[] Boxes => boxes;              // Boxes Getter
+    public ulong Id => id;                      // Id Getter
+    public ulong BoxesHash => boxesHash;        // BoxesHash Getter
 
-    // Id Getter and Setter
-    public string Id {
-        get => id;
-        set => id = value;
-    }
-
-    // Determines the coordinates of player
-    private (int, int) ObtainPlayerCoords(string level) {
+    // Determines the cell of player
+    private int ObtainPlayerCell(string level, Level levelInfo) {
 
         int playerCount = 0;
         int i = 0, j = 0;
@@ -75,23 +64,94 @@ public class State {
             throw new InvalidOperationException("No player found in the level.");
         }
 
-        return (x, y);
+        return levelInfo.Cell(x, y);
+    }
+
+    // Returns if there is a box on the cell
+    public bool HasBox(int cell) {
+        return Array.BinarySearch(boxes, (short)cell) >= 0;
+    }
+
+    // Returns the state reached when the player walks to a cell (boxes are shared)
+    public State MovePlayer(Level level, int cell) {
+        return new State(cell, boxes, boxesHash, level);
     }
 
-    public void MovePlayer(int x, int y) {
-        player = (x, y);  // Set the player to the new position
+    // Returns the state reached when the player pushes the box on one cell to the next one
+    public State PushBox(Level level, int from, int to) {
+        return MoveBox(level, from, to, from);
     }
 
-    // Determines the coordinates of boxes that are already on target
-    private int[][] ObtainBoxesCoords(string level) {
+    // Returns the state reached when the player pulls the box on one cell to the next one (backward search)
+    public State PullBox(Level level, int from, int to) {
+        return MoveBox(level, from, to, to + (to - from));
+    }
+
+    // Returns the state with the box on one cell moved to another and the player on a new cell
+    private State MoveBox(Level level, int from, int to, int newPlayer) {
+
+        short[] newBoxes = new short[boxes.Length];
+        int index = Array.BinarySearch(boxes, (short)from);
+
+        Array.Copy(boxes, newBoxes, boxes.Length);
+        newBoxes[index] = (short)to;
+
+        // Shift the moved box to keep the cells sorted
+        while (index > 0 && newBoxes[index - 1] > newBoxes[index]) {
+            (newBoxes[index - 1], newBoxes[index]) = (newBoxes[index], newBoxes[index - 1]);
+            index--;
+        }
+
+        while (index < newBoxes.Length - 1 && newBoxes[index + 1] < newBoxes[index]) {
+            (newBoxes[index + 1], newBoxes[index]) = (newBoxes[index], newBoxes[index + 1]);
+            index++;
+        }
+
+        ulong newBoxesHash = boxesHash ^ level.ZobristBox(from) ^ level.ZobristBox(to);
+        return new State(newPlayer, newBoxes, newBoxesHash, level);
+    }
+
+    // Recalculates the id with the player moved to the top-left-most cell it can reach,
+    // so states that only differ in where the player stands inside the same region share the id
+    public void NormalizeId(Level level) {
+        UpdateId(level, NormalizedPlayer(level));
+    }
+
+    // Returns if both states have the same boxes and the same hashed player cell
+    public bool SameAs(State other) {
+        return idPlayer == other.idPlayer && boxes.SequenceEqual(other.boxes);
+    }
+
+    // Combines the boxes hash with the player cell
+    private void UpdateId(Level level, int hashedPlayer) {
+        idPlayer = hashedPlayer;
+        id = boxesHash ^ level.ZobristPlayer(hashedPlayer);
+    }
+
+    // Returns the top-left-most cell the player can walk to without pushing any box
+    public int NormalizedPlayer(Level level) {
+
+        int[] reachable = level.ReachableRegion(this);
+
+        // Cells are numbered row by row, the first reachable one is the top-left-most
+        for (int cell = 0; cell < reachable.Length; cell++) {
+            if (reachable[cell] != -1) {
+                return cell;
+            }
+        }
+
+        return player;
+    }
+
+    // Determines the sorted cells of boxes (including the ones already on target)
+    private short[] ObtainBoxesCells(string level, Level levelInfo) {
 
         int i = 0, j = 0;
-        List<int[]> boxesCoords = new List<int[]>();
-        int[][] boxes;
+        List<short> boxesCells = new List<short>();
 
         foreach (char c in level) {
             if (c == '$' || c == '*') {
-                boxesCoords.Add(new int[] { i, j });
+                boxesCells.Add((short)levelInfo.Cell(i, j));
                 j++;
             }
             else if (c == '\n') {
@@ -103,39 +163,7 @@ public class State {
             }
         }
 
-        boxes = boxesCoords.OrderBy(box => box[0])
-                            .ThenBy(box => box[1])
-                            .ToArray();
-        return boxes;
-    }
-
-    // Generates a MD5 hash based on the player and box position
-    public string CalculateMD5Hash((int, int) player, int[][] boxes) {
-
-        string input = $"({player.Item1},{player.Item2})[";
-
-        for (int i = 0; i < boxes.Length; i++) {
-            input += $"({String.Join(",", boxes[i])})";
-            if (i < boxes.Length - 1) {
-                input += ",";
-            }
-        }
-        input += "]";
-
-        using (MD5 md5 = MD5.Create()) {
-            // Convert string to bytes
-            byte[] inputBytes = Encoding.UTF8.GetBytes(input);
-
-            // Compute MD5 hash
-            byte[] hashBytes = md5.ComputeHash(inputBytes);
-
-            // Convert byte array to hexadecimal string
-            StringBuilder sb = new StringBuilder();
-            for (int i = 0; i < hashBytes.Length; i++) {
-                sb.Append(hashBytes[i].ToString("X2")); // X2 formats to hexadecimal
-            }
-
-            return sb.ToString();
-        }
+        boxesCells.Sort();
+        return boxesCells.ToArray();
     }
 }
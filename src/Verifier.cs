@@ -0,0 +1,111 @@
+namespace Sokoban;
+
+public class Verifier {
+
+    private bool legal = true;
+    private bool solved;
+    private int illegalMove = -1;
+    private int movesCount;
+    private int pushesCount;
+    private string malformed = "";
+
+    // Verifier constructor 1 (replays the LURD moves from the initial state)
+    public Verifier(Level level, State state, string moves) {
+
+        foreach (char move in moves) {
+            State? next = TryMove(level, state, move);
+
+            if (next == null) {
+                legal = false;
+                illegalMove = movesCount;
+                break;
+            }
+
+            state = next;
+            movesCount++;
+
+            if (char.IsUpper(move)) {
+                pushesCount++;
+            }
+        }
+
+        solved = legal && Program.ObjectiveFunction(level, state);
+    }
+
+    // Verifier constructor 2 (moves that could not be decoded, nothing is replayed)
+    private Verifier(string malformed) {
+        legal = false;
+        illegalMove = 0;
+        this.malformed = malformed;
+    }
+
+    // Returns the verifier of run-length encoded LURD moves, malformed run counts are reported as illegal
+    public static Verifier Decode(Level level, State state, string rle) {
+        try {
+            return new Verifier(level, state, Rle.Decode(rle));
+        }
+        catch (FormatException e) {
+            return new Verifier(e.Message);
+        }
+    }
+
+    // Properties
+    public bool Legal => legal;                 // Legal replay Getter
+    public bool Solved => solved;               // Solved Getter (legal and every box on a target)
+    public int IllegalMove => illegalMove;      // 0-based index of the first illegal move Getter (-1 if legal)
+    public int MovesCount => movesCount;        // Replayed moves Getter
+    public int PushesCount => pushesCount;      // Replayed pushes Getter
+    public string Malformed => malformed;       // Decoding error Getter (empty if the moves were decoded)
+
+    // Returns the state reached after a single LURD move, or null if the move is illegal.
+    // Upper-case moves must push a box onto a free cell, lower-case moves must walk onto a free cell.
+    // Pushes onto dead squares are legal moves, they are only pruned by the search.
+    private static State? TryMove(Level level, State state, char move) {
+
+        if ("urdlURDL".IndexOf(move) < 0) {
+            return null;
+        }
+
+        int direction = level.Direction(move);
+        int playerMove = state.Player + direction;
+
+        // Check wall collision
+        if (level.IsWall(playerMove)) {
+            return null;
+        }
+
+        if (char.IsUpper(move)) {
+            int boxMove = playerMove + direction;
+
+            // Check there is a box to push and its new position is empty
+            if (!state.HasBox(playerMove) || level.IsWall(boxMove) || state.HasBox(boxMove)) {
+                return null;
+            }
+
+            return state.PushBox(level, playerMove, boxMove);
+        }
+
+        // Walking into a box must be written as a push
+        if (state.HasBox(playerMove)) {
+            return null;
+        }
+
+        return state.MovePlayer(level, playerMove);
+    }
+
+    // Prints the verification report
+    public void PrintReport() {
+        Console.WriteLine("Legal: " + (legal ? "yes" : "no"));
+
+        if (malformed != "") {
+            Console.WriteLine("Malformed moves: " + malformed);
+        }
+        else if (!legal) {
+            Console.WriteLine("First illegal move: " + illegalMove);
+        }
+
+        Console.WriteLine("Solved: " + (solved ? "yes" : "no"));
+        Console.WriteLine("Moves: " + movesCount);
+        Console.WriteLine("Pushes: " + pushesCount);
+    }
+}
@@ -0,0 +1,48 @@
+namespace Sokoban;
+
+public class VisitedSet {
+
+    // States grouped by Zobrist id, colliding ids are told apart by comparing the full state
+    private Dictionary<ulong, List<State>> states;
+    private int count;
+
+    // VisitedSet constructor
+    public VisitedSet() {
+        states = new Dictionary<ulong, List<State>>();
+        count = 0;
+    }
+
+    public int Count => count;      // Visited states count
+
+    // Returns if an equal state was already visited
+    public bool Contains(State state) {
+
+        if (!states.TryGetValue(state.Id, out List<State>? sameId)) {
+            return false;
+        }
+
+        foreach (var visitedState in sameId) {
+            if (visitedState.SameAs(state)) {
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    // Adds the state, returns false if an equal state was already visited
+    public bool Add(State state) {
+
+        if (!states.TryGetValue(state.Id, out List<State>? sameId)) {
+            sameId = new List<State>();
+            states[state.Id] = sameId;
+        }
+        else if (sameId.Any(visitedState => visitedState.SameAs(state))) {
+            return false;
+        }
+
+        sameId.Add(state);
+        count++;
+        return true;
+    }
+}
@@ -0,0 +1,47 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class BatchTests {
+
+    [Fact]
+    public void QuotesCsvFieldsWithCommasOrQuotes() {
+        Assert.Equal("Corridor", Program.CsvField("Corridor"));
+        Assert.Equal("\"Easy, first\"", Program.CsvField("Easy, first"));
+        Assert.Equal("\"The \"\"long\"\" one\"", Program.CsvField("The \"long\" one"));
+    }
+
+    [Fact]
+    public void WritesOneCsvRowPerLevel() {
+        string collectionPath = Path.Combine(Path.GetTempPath(), Guid.NewGuid() + ".xsb");
+        string csvPath = Path.Combine(Path.GetTempPath(), Guid.NewGuid() + ".csv");
+
+        File.WriteAllLines(collectionPath, new[] {
+            "Easy, first",
+            "#######",
+            "#@ $ .#",
+            "#######",
+            "",
+            "Cornered",
+            "#####",
+            "#$@.#",
+            "#####",
+        });
+
+        try {
+            int exitCode = Program.Main(new[] { "batch", collectionPath, "BFS", "50", "--csv", csvPath, });
+            string[] csv = File.ReadAllLines(csvPath);
+
+            // The cornered box can't be solved
+            Assert.Equal(1, exitCode);
+            Assert.Equal(3, csv.Length);
+            Assert.Equal("index,title,status,moves,pushes,nodes,time_ms", csv[0]);
+            Assert.StartsWith("1,\"Easy, first\",solved,3,2,", csv[1]);
+            Assert.StartsWith("2,Cornered,unsolvable,,,", csv[2]);
+        }
+        finally {
+            File.Delete(collectionPath);
+            File.Delete(csvPath);
+        }
+    }
+}
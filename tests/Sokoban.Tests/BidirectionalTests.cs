@@ -0,0 +1,42 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class BidirectionalTests {
+
+    private const string LEVEL =
+        "######\n" +
+        "# .  #\n" +
+        "#@$$ #\n" +
+        "#   .#\n" +
+        "######";
+
+    private const string SOLVED_LEVEL =
+        "#####\n" +
+        "#@* #\n" +
+        "#####";
+
+    [Fact]
+    public void SolvesSmallLevel() {
+        Param param = new Param(new[] { LEVEL, "BIDIR", "50", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.Solved, result.Outcome);
+
+        string moves = string.Concat(result.SolutionPath!.Skip(1).Select(node => node.Action));
+        Assert.True(new Verifier(level, state, moves).Solved);
+    }
+
+    [Fact]
+    public void SolvedLevelNeedsNoSearch() {
+        Param param = new Param(new[] { SOLVED_LEVEL, "BIDIR", "50", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.Solved, result.Outcome);
+        Assert.Single(result.SolutionPath!);
+    }
+}
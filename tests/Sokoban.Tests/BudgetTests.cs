@@ -0,0 +1,36 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class BudgetTests {
+
+    private const string LEVEL =
+        "######\n" +
+        "#.   #\n" +
+        "# $  #\n" +
+        "#@ $.#\n" +
+        "######";
+
+    [Fact]
+    public void NodeLimitStopsTheSearch() {
+        Param param = new Param(new[] { LEVEL, "BFS", "50", "--nodes", "3", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.LimitReached, result.Outcome);
+        Assert.Equal("nodes", result.Statistics.ExceededBudget);
+        Assert.Null(result.SolutionPath);
+        Assert.NotNull(result.BestNode);
+    }
+
+    [Fact]
+    public void NodeLimitStopsIterativeDeepening() {
+        Param param = new Param(new[] { LEVEL, "IDA*", "50", "--nodes", "3", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.LimitReached, result.Outcome);
+    }
+}
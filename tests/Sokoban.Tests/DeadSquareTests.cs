@@ -0,0 +1,54 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class DeadSquareTests {
+
+    // One target on the right wall, every other cell along the walls is a corner or a wall run without target
+    private const string LEVEL =
+        "######\n" +
+        "#@   #\n" +
+        "# $ .#\n" +
+        "#    #\n" +
+        "######";
+
+    [Fact]
+    public void CornersAreDead() {
+        Level level = new Level(LEVEL);
+
+        Assert.True(level.IsDeadSquare(1, 1));
+        Assert.True(level.IsDeadSquare(1, 4));
+        Assert.True(level.IsDeadSquare(3, 1));
+        Assert.True(level.IsDeadSquare(3, 4));
+    }
+
+    [Fact]
+    public void WallRunsWithoutTargetAreDead() {
+        Level level = new Level(LEVEL);
+
+        Assert.True(level.IsDeadSquare(1, 2));
+        Assert.True(level.IsDeadSquare(1, 3));
+        Assert.True(level.IsDeadSquare(2, 1));
+        Assert.True(level.IsDeadSquare(3, 2));
+        Assert.True(level.IsDeadSquare(3, 3));
+    }
+
+    [Fact]
+    public void TargetsAndOpenCellsAreNotDead() {
+        Level level = new Level(LEVEL);
+
+        Assert.False(level.IsDeadSquare(2, 4));
+        Assert.False(level.IsDeadSquare(2, 2));
+        Assert.False(level.IsDeadSquare(2, 3));
+        Assert.False(level.IsDeadSquare(level.Cell(2, 4)));
+    }
+
+    [Fact]
+    public void WallsAndCellsOutsideTheLevelAreNotDead() {
+        Level level = new Level(LEVEL);
+
+        Assert.False(level.IsDeadSquare(0, 0));
+        Assert.False(level.IsDeadSquare(-1, 2));
+        Assert.False(level.IsDeadSquare(2, 6));
+    }
+}
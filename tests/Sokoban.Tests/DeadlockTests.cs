@@ -0,0 +1,53 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class DeadlockTests {
+
+    // Two boxes side by side against the top wall, the targets keep the top row free of dead squares
+    private const string FROZEN_PAIR_LEVEL =
+        "########\n" +
+        "#  $$ .#\n" +
+        "#      #\n" +
+        "#@    .#\n" +
+        "########";
+
+    // The same pair with both boxes on targets
+    private const string FROZEN_PAIR_ON_TARGETS_LEVEL =
+        "########\n" +
+        "#  **  #\n" +
+        "#      #\n" +
+        "#@     #\n" +
+        "########";
+
+    // A single box against the top wall, it can still be pushed along the row
+    private const string ONE_AXIS_LEVEL =
+        "########\n" +
+        "#  $  .#\n" +
+        "#      #\n" +
+        "#@    .#\n" +
+        "########";
+
+    [Fact]
+    public void BoxesFrozenAgainstEachOtherOffTargetAreDeadlocked() {
+        (Level level, State state) = Program.CreateLevel(FROZEN_PAIR_LEVEL);
+
+        Assert.True(Deadlock.IsFreezeDeadlock(level, state, level.Cell(1, 3)));
+        Assert.True(Deadlock.IsFreezeDeadlock(level, state, level.Cell(1, 4)));
+    }
+
+    [Fact]
+    public void BoxesFrozenOnTargetsAreNotDeadlocked() {
+        (Level level, State state) = Program.CreateLevel(FROZEN_PAIR_ON_TARGETS_LEVEL);
+
+        Assert.False(Deadlock.IsFreezeDeadlock(level, state, level.Cell(1, 3)));
+        Assert.False(Deadlock.IsFreezeDeadlock(level, state, level.Cell(1, 4)));
+    }
+
+    [Fact]
+    public void BoxBlockedOnOneAxisIsNotDeadlocked() {
+        (Level level, State state) = Program.CreateLevel(ONE_AXIS_LEVEL);
+
+        Assert.False(Deadlock.IsFreezeDeadlock(level, state, level.Cell(1, 3)));
+    }
+}
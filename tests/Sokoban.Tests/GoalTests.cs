@@ -0,0 +1,56 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class GoalTests {
+
+    // Two boxes and three targets: any two targets holding both boxes solve the level
+    private const string SURPLUS_TARGETS_LEVEL =
+        "#######\n" +
+        "#@    #\n" +
+        "# $$  #\n" +
+        "# ... #\n" +
+        "#######";
+
+    [Fact]
+    public void SurplusTargetsAreSolvedWhenEveryBoxIsOnATarget() {
+        (Level level, State state) = Program.CreateLevel(SURPLUS_TARGETS_LEVEL);
+
+        Assert.False(Program.ObjectiveFunction(level, state));
+
+        // Boxes on the first and the last target, the middle one left empty
+        short[] boxes = { (short)level.Cell(3, 2), (short)level.Cell(3, 4), };
+        State goal = new State(level.Cell(1, 1), boxes, level.ZobristBoxesHash(boxes), level);
+        Assert.True(Program.ObjectiveFunction(level, goal));
+
+        // One box on a target, the other one next to it
+        boxes = new short[] { (short)level.Cell(2, 4), (short)level.Cell(3, 2), };
+        State partial = new State(level.Cell(1, 1), boxes, level.ZobristBoxesHash(boxes), level);
+        Assert.False(Program.ObjectiveFunction(level, partial));
+    }
+
+    [Fact]
+    public void GoalStatesCoverEveryTargetSubset() {
+        (Level level, State state) = Program.CreateLevel(SURPLUS_TARGETS_LEVEL);
+
+        // C(3, 2) subsets, the player can reach every free cell in each of them
+        Assert.Equal(3, Program.GoalStates(level, state).Count);
+    }
+
+    [Fact]
+    public void SolvesLevelWithSurplusTargets() {
+        Param param = new Param(new[] { SURPLUS_TARGETS_LEVEL, "BIDIR", "50", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.Solved, result.Outcome);
+    }
+
+    [Fact]
+    public void CountsGoalSubsets() {
+        Assert.Equal(1, Program.GoalSubsetsCount(4, 4));
+        Assert.Equal(3, Program.GoalSubsetsCount(3, 2));
+        Assert.Equal(184756, Program.GoalSubsetsCount(20, 10));
+    }
+}
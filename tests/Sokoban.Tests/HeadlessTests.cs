@@ -0,0 +1,40 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class HeadlessTests {
+
+    // Shortest solution: 7 moves
+    private const string LEVEL =
+        "######\\n" +
+        "#.   #\\n" +
+        "# $  #\\n" +
+        "#@ $.#\\n" +
+        "######";
+
+    // The box starts in a corner
+    private const string UNSOLVABLE_LEVEL =
+        "#####\\n" +
+        "#$@.#\\n" +
+        "#####";
+
+    [Fact]
+    public void SolvedExitsWithZero() {
+        Assert.Equal(0, Program.Main(new[] { LEVEL, "BFS", "50", "--headless", }));
+    }
+
+    [Fact]
+    public void ProvenUnsolvableExitsWithOne() {
+        Assert.Equal(1, Program.Main(new[] { UNSOLVABLE_LEVEL, "BFS", "50", "--headless", }));
+    }
+
+    [Fact]
+    public void DepthBoundExitsWithTwo() {
+        Assert.Equal(2, Program.Main(new[] { LEVEL, "BFS", "3", "--headless", }));
+    }
+
+    [Fact]
+    public void NodeLimitExitsWithTwo() {
+        Assert.Equal(2, Program.Main(new[] { LEVEL, "BFS", "50", "--headless", "--nodes", "2", }));
+    }
+}
@@ -0,0 +1,50 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class HungarianTests {
+
+    [Fact]
+    public void MatchesFewerRowsThanColumns() {
+        // Two boxes, three targets: the cheapest targets of both boxes would clash on column 1
+        int[,] costs = {
+            { 4, 1, 6 },
+            { 2, 0, 5 },
+        };
+
+        Assert.Equal(3, Hungarian.MinimumCost(costs));
+    }
+
+    [Fact]
+    public void MatchesSquareMatrix() {
+        int[,] costs = {
+            { 9, 2, 7 },
+            { 6, 4, 3 },
+            { 5, 8, 1 },
+        };
+
+        Assert.Equal(9, Hungarian.MinimumCost(costs));
+    }
+
+    [Fact]
+    public void AvoidsInfiniteDistances() {
+        int inf = Level.INFINITE_DISTANCE;
+        int[,] costs = {
+            { inf, 2, 7 },
+            { 3, inf, inf },
+        };
+
+        Assert.Equal(5, Hungarian.MinimumCost(costs));
+    }
+
+    [Fact]
+    public void KeepsUnavoidableInfiniteDistance() {
+        int inf = Level.INFINITE_DISTANCE;
+        int[,] costs = {
+            { inf, inf },
+            { 1, 2 },
+        };
+
+        Assert.Equal(inf + 1, Hungarian.MinimumCost(costs));
+    }
+}
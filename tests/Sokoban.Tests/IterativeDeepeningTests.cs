@@ -0,0 +1,37 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class IterativeDeepeningTests {
+
+    // Shortest solution: 7 moves
+    private const string LEVEL =
+        "######\n" +
+        "#.   #\n" +
+        "# $  #\n" +
+        "#@ $.#\n" +
+        "######";
+
+    // Returns the number of moves of the solution found by a strategy
+    private static int SolutionLength(string strategy) {
+        Param param = new Param(new[] { LEVEL, strategy, "50", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.Solved, result.Outcome);
+        return result.SolutionPath!.Count - 1;
+    }
+
+    [Fact]
+    public void IddfsFindsTheBfsSolutionLength() {
+        Assert.Equal(7, SolutionLength("BFS"));
+        Assert.Equal(7, SolutionLength("IDDFS"));
+    }
+
+    [Fact]
+    public void IdaStarFindsTheAStarSolutionLength() {
+        Assert.Equal(7, SolutionLength("A*"));
+        Assert.Equal(7, SolutionLength("IDA*"));
+    }
+}
@@ -0,0 +1,69 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class LevelFileTests {
+
+    private static readonly string[] COLLECTION = {
+        "; Two levels",
+        "",
+        "First",
+        "#####",
+        "#@$.#",
+        "#####",
+        "Author: Jane Doe",
+        "Comment: Warm-up",
+        "; Push right once",
+        "",
+        "#######",
+        "#@-$-.#",
+        "#######",
+        "Title: Second",
+        "",
+    };
+
+    [Fact]
+    public void ParsesBoardsAndMetadata() {
+        List<LevelFile> levels = LevelFile.Parse(COLLECTION);
+
+        Assert.Equal(2, levels.Count);
+
+        Assert.Equal("#####\n#@$.#\n#####", levels[0].Level);
+        Assert.Equal("First", levels[0].Title);
+        Assert.Equal("Jane Doe", levels[0].Author);
+        Assert.Equal("Warm-up\nPush right once", levels[0].Comment);
+
+        Assert.Equal("#######\n#@ $ .#\n#######", levels[1].Level);
+        Assert.Equal("Second", levels[1].Title);
+        Assert.Equal("", levels[1].Author);
+    }
+
+    [Fact]
+    public void SelectsByIndexAndTitle() {
+        List<LevelFile> levels = LevelFile.Parse(COLLECTION);
+
+        Assert.Same(levels[1], LevelFile.Select(levels, "2"));
+        Assert.Same(levels[1], LevelFile.Select(levels, "second"));
+        Assert.Throws<ArgumentException>(() => LevelFile.Select(levels, "3"));
+        Assert.Throws<ArgumentException>(() => LevelFile.Select(levels, "Third"));
+    }
+
+    [Fact]
+    public void ParamLoadsTheSelectedLevelOfAFile() {
+        string path = Path.Combine(Path.GetTempPath(), Guid.NewGuid() + ".xsb");
+        File.WriteAllLines(path, COLLECTION);
+
+        try {
+            Param byIndex = new Param(new[] { path, "BFS", "10", });
+            Assert.Equal("First", byIndex.LevelTitle);
+            Assert.Equal("#####\n#@$.#\n#####", byIndex.Level);
+
+            Param byTitle = new Param(new[] { path, "BFS", "10", "--level", "Second", });
+            Assert.Equal("Second", byTitle.LevelTitle);
+            Assert.Equal("#######\n#@ $ .#\n#######", byTitle.Level);
+        }
+        finally {
+            File.Delete(path);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class ObjectiveTests {
+
+    // Fewest moves: 8 moves and 4 pushes. Fewest pushes: 2 pushes and 10 moves.
+    private const string LEVEL =
+        "#######\n" +
+        "# #   #\n" +
+        "# @$  #\n" +
+        "# .   #\n" +
+        "#######";
+
+    // Returns the moves and pushes of the solution found by UC for an objective
+    private static (int, int) Solve(string objective) {
+        Param param = new Param(new[] { LEVEL, "UC", "50", "--objective", objective, });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+        string moves = Program.SolutionMoves(result.SolutionPath!);
+
+        return (moves.Length, Program.PushesCount(moves));
+    }
+
+    [Fact]
+    public void CostsOfEveryObjective() {
+        Assert.Equal(8.0, Node.ObjectiveCost("MOVES", 8, 4));
+        Assert.Equal(4.0, Node.ObjectiveCost("PUSHES", 8, 4));
+        Assert.Equal(8.0 * Node.LEXICOGRAPHIC_WEIGHT + 4, Node.ObjectiveCost("MOVES_PUSHES", 8, 4));
+        Assert.Equal(4.0 * Node.LEXICOGRAPHIC_WEIGHT + 8, Node.ObjectiveCost("PUSHES_MOVES", 8, 4));
+    }
+
+    [Fact]
+    public void MovesObjectiveMinimisesMoves() {
+        Assert.Equal((8, 4), Solve("MOVES"));
+        Assert.Equal((8, 4), Solve("MOVES_PUSHES"));
+    }
+
+    [Fact]
+    public void PushesObjectiveMinimisesPushes() {
+        Assert.Equal(2, Solve("PUSHES").Item2);
+        Assert.Equal((10, 2), Solve("PUSHES_MOVES"));
+    }
+}
@@ -0,0 +1,29 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class OptimizerTests {
+
+    // Six pushes along the corridor, so the push windows slide over the solution
+    private const string LEVEL =
+        "##########\n" +
+        "#@$     .#\n" +
+        "##########";
+
+    [Fact]
+    public void RemovesDetoursBetweenPushes() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        string optimized = Program.OptimizeSolution(level, state, "RlrRRlrRRR");
+
+        Assert.Equal("RRRRRR", optimized);
+        Assert.True(new Verifier(level, state, optimized).Solved);
+    }
+
+    [Fact]
+    public void KeepsMovesThatDoNotSolveTheLevel() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        Assert.Equal("RR", Program.OptimizeSolution(level, state, "RR"));
+    }
+}
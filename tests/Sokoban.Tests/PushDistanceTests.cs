@@ -0,0 +1,48 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class PushDistanceTests {
+
+    // The wall between the box and the target makes the box go around it
+    private const string AROUND_WALL_LEVEL =
+        "#######\n" +
+        "#     #\n" +
+        "# $#. #\n" +
+        "#     #\n" +
+        "#@    #\n" +
+        "#######";
+
+    // The box blocks the doorway between two rooms, the player can't walk around it to push it up
+    private const string DOORWAY_LEVEL =
+        "#########\n" +
+        "#  .#   #\n" +
+        "#   $ @ #\n" +
+        "#   #   #\n" +
+        "#########";
+
+    [Fact]
+    public void BoxGoesAroundWall() {
+        Level level = new Level(AROUND_WALL_LEVEL);
+
+        // Manhattan distance is 2, the box is pushed down, right twice and up
+        Assert.Equal(4, level.NearestPushDistance(level.Cell(2, 2)));
+    }
+
+    [Fact]
+    public void PlayerCantCrossTheBox() {
+        Level level = new Level(DOORWAY_LEVEL);
+
+        // Pushing left then up would take 2 pushes, but the player can't reach the cell below the box
+        Assert.Equal(4, level.NearestPushDistance(level.Cell(2, 4)));
+        Assert.Equal(0, level.NearestPushDistance(level.Cell(1, 3)));
+    }
+
+    [Fact]
+    public void DistanceToEveryTarget() {
+        Level level = new Level(AROUND_WALL_LEVEL);
+
+        Assert.Equal(4, level.PushDistance(level.Cell(2, 2), 0));
+        Assert.Equal(Level.INFINITE_DISTANCE, level.PushDistance(level.Cell(1, 1), 0));
+    }
+}
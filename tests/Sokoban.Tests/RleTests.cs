@@ -0,0 +1,37 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class RleTests {
+
+    [Fact]
+    public void EncodedLevelDecodesBackToTheLevel() {
+        string level = "#######\n#@ $ .#\n#######";
+
+        string encoded = Rle.EncodeLevel(level);
+
+        Assert.Equal("7#|#@-$-.#|7#", encoded);
+        Assert.True(Rle.IsEncodedLevel(encoded));
+        Assert.Equal(level, Rle.DecodeLevel(encoded));
+    }
+
+    [Fact]
+    public void ParamDecodesRunLengthEncodedLevels() {
+        Param param = new Param(new[] { "7#|#@-$-.#|7#", "BFS", "10", });
+
+        Assert.Equal("#######\n#@ $ .#\n#######", param.Level);
+    }
+
+    [Fact]
+    public void ParamKeepsPlainLevels() {
+        Param param = new Param(new[] { "#######\\n#@ $ .#\\n#######", "BFS", "10", });
+
+        Assert.Equal("#######\n#@ $ .#\n#######", param.Level);
+    }
+
+    [Fact]
+    public void EncodesRunsOfMoves() {
+        Assert.Equal("3r2Ul", Rle.Encode("rrrUUl"));
+        Assert.Equal("rrrUUl", Rle.Decode("3r2Ul"));
+    }
+}
@@ -0,0 +1,49 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class SearchOutcomeTests {
+
+    // Shortest solution: 7 moves
+    private const string LEVEL =
+        "######\n" +
+        "#.   #\n" +
+        "# $  #\n" +
+        "#@ $.#\n" +
+        "######";
+
+    // The box starts in a corner, the player can only walk between two cells
+    private const string UNSOLVABLE_LEVEL =
+        "#####\n" +
+        "#$@.#\n" +
+        "#####";
+
+    // Returns the outcome of a search
+    private static SearchOutcome Outcome(string levelString, string strategy, string depth) {
+        Param param = new Param(new[] { levelString, strategy, depth, });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        return Program.Search(level, state, param).Outcome;
+    }
+
+    [Theory]
+    [InlineData("BFS")]
+    [InlineData("IDDFS")]
+    public void DepthBoundBelowTheSolutionIsInconclusive(string strategy) {
+        Assert.Equal(SearchOutcome.Inconclusive, Outcome(LEVEL, strategy, "3"));
+    }
+
+    [Theory]
+    [InlineData("BFS")]
+    [InlineData("IDDFS")]
+    public void ExhaustedSearchIsUnsolvable(string strategy) {
+        Assert.Equal(SearchOutcome.Unsolvable, Outcome(UNSOLVABLE_LEVEL, strategy, "50"));
+    }
+
+    [Fact]
+    public void StatesAlreadyVisitedAtTheBoundAreNotPruned() {
+        // The only node at depth 1 is cut by the bound, at depth 2 its single child walks back to the root state
+        Assert.Equal(SearchOutcome.Inconclusive, Outcome(UNSOLVABLE_LEVEL, "BFS", "1"));
+        Assert.Equal(SearchOutcome.Unsolvable, Outcome(UNSOLVABLE_LEVEL, "BFS", "2"));
+    }
+}
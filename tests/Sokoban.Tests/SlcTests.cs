@@ -0,0 +1,58 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class SlcTests {
+
+    [Fact]
+    public void SavedLevelsLoadBackWithTheirMetadata() {
+        string path = Path.Combine(Path.GetTempPath(), Guid.NewGuid() + ".slc");
+
+        LevelFile solved = new LevelFile("#######\n#@ $ .#\n#######", "Corridor");
+        solved.Author = "Jane Doe";
+        solved.Comment = "Push right";
+        solved.Solution = "rRR";
+
+        LevelFile unsolved = new LevelFile("#####\n#@$.#\n#####", "");
+
+        try {
+            Slc.Save(path, "Collection", new List<LevelFile> { solved, unsolved });
+            List<LevelFile> levels = Slc.Load(path);
+
+            Assert.Equal(2, levels.Count);
+
+            Assert.Equal(solved.Level, levels[0].Level);
+            Assert.Equal("Corridor", levels[0].Title);
+            Assert.Equal("Jane Doe", levels[0].Author);
+            Assert.Equal("Push right", levels[0].Comment);
+            Assert.Equal("rRR", levels[0].Solution);
+
+            Assert.Equal(unsolved.Level, levels[1].Level);
+            Assert.Equal("", levels[1].Title);
+            Assert.Equal("", levels[1].Author);
+            Assert.Equal("", levels[1].Comment);
+            Assert.Equal("", levels[1].Solution);
+        }
+        finally {
+            File.Delete(path);
+        }
+    }
+
+    [Fact]
+    public void CommandLineLevelIsNamedAfterTheExportFile() {
+        string path = Path.Combine(Path.GetTempPath(), "corridor-" + Guid.NewGuid().ToString("N") + ".slc");
+
+        try {
+            int exitCode = Program.Main(new[] { "#######\\n#@ $ .#\\n#######", "BFS", "10", "--headless", "--export", path, });
+            List<LevelFile> levels = Slc.Load(path);
+
+            Assert.Equal(0, exitCode);
+            Assert.Single(levels);
+            Assert.Equal(Path.GetFileNameWithoutExtension(path), levels[0].Title);
+            Assert.Equal("rRR", levels[0].Solution);
+        }
+        finally {
+            File.Delete(path);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class SolutionTests {
+
+    // Shortest solution: 7 moves, 3 of them pushes
+    private const string LEVEL =
+        "######\n" +
+        "#.   #\n" +
+        "# $  #\n" +
+        "#@ $.#\n" +
+        "######";
+
+    [Fact]
+    public void CountsUpperCaseMovesAsPushes() {
+        Assert.Equal(0, Program.PushesCount(""));
+        Assert.Equal(0, Program.PushesCount("urdl"));
+        Assert.Equal(3, Program.PushesCount("rUdRuuL"));
+    }
+
+    [Theory]
+    [InlineData(false)]
+    [InlineData(true)]
+    public void SolutionMovesMarkEveryPush(bool pushLevel) {
+        List<string> args = new List<string> { LEVEL, "BFS", "50", };
+
+        if (pushLevel) {
+            args.Add("--push");
+        }
+
+        Param param = new Param(args.ToArray());
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        List<Node> path = Program.Search(level, state, param).SolutionPath!;
+        string moves = Program.SolutionMoves(path);
+
+        // One LURD character per step, upper-case exactly where the boxes moved
+        Assert.Equal(path.Count - 1, moves.Length);
+
+        for (int i = 1; i < path.Count; i++) {
+            bool pushed = !path[i].StateNode.Boxes.SequenceEqual(path[i - 1].StateNode.Boxes);
+            Assert.Equal(pushed, char.IsUpper(moves[i - 1]));
+        }
+
+        Verifier verifier = new Verifier(level, state, moves);
+        Assert.True(verifier.Solved);
+        Assert.Equal(Program.PushesCount(moves), verifier.PushesCount);
+        Assert.Equal(3, verifier.PushesCount);
+    }
+}
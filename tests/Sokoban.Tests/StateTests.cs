@@ -0,0 +1,58 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class StateTests {
+
+    private const string LEVEL =
+        "#######\n" +
+        "#@    #\n" +
+        "# $$  #\n" +
+        "#  .. #\n" +
+        "#     #\n" +
+        "#######";
+
+    [Fact]
+    public void PushedStateIdMatchesIdFromScratch() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        State pushed = state.MovePlayer(level, level.Cell(1, 2)).PushBox(level, level.Cell(2, 2), level.Cell(3, 2));
+        State scratch = new State(pushed.Player, pushed.Boxes, level.ZobristBoxesHash(pushed.Boxes), level);
+
+        Assert.Equal(level.Cell(2, 2), pushed.Player);
+        Assert.Equal(scratch.BoxesHash, pushed.BoxesHash);
+        Assert.Equal(scratch.Id, pushed.Id);
+    }
+
+    [Fact]
+    public void PulledStateIdMatchesIdFromScratch() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        // Player below the right box pulls it down, stepping back one more cell
+        State pulled = state.MovePlayer(level, level.Cell(3, 3)).PullBox(level, level.Cell(2, 3), level.Cell(3, 3));
+        State scratch = new State(pulled.Player, pulled.Boxes, level.ZobristBoxesHash(pulled.Boxes), level);
+
+        Assert.Equal(level.Cell(4, 3), pulled.Player);
+        Assert.Equal(scratch.BoxesHash, pulled.BoxesHash);
+        Assert.Equal(scratch.Id, pulled.Id);
+    }
+
+    [Fact]
+    public void VisitedSetTellsCollidingIdsApart() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        // Different boxes forced onto the same boxes hash, so both states share the id
+        short[] boxes = { (short)level.Cell(2, 2), (short)level.Cell(2, 4), };
+        State colliding = new State(state.Player, boxes, state.BoxesHash, level);
+        Assert.Equal(state.Id, colliding.Id);
+
+        VisitedSet visited = new VisitedSet();
+        Assert.True(visited.Add(state));
+        Assert.False(visited.Contains(colliding));
+        Assert.True(visited.Add(colliding));
+        Assert.False(visited.Add(state));
+
+        Assert.True(visited.Contains(colliding));
+        Assert.Equal(2, visited.Count);
+    }
+}
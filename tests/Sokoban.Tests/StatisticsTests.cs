@@ -0,0 +1,38 @@
+using System.Text.Json;
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class StatisticsTests {
+
+    private const string LEVEL =
+        "######\n" +
+        "#.   #\n" +
+        "# $  #\n" +
+        "#@ $.#\n" +
+        "######";
+
+    [Fact]
+    public void JsonHasEveryCounter() {
+        Param param = new Param(new[] { LEVEL, "A*", "50", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+        SearchStatistics statistics = Program.Search(level, state, param).Statistics;
+
+        using JsonDocument document = JsonDocument.Parse(statistics.ToJson());
+        JsonElement root = document.RootElement;
+
+        Assert.Equal(JsonValueKind.Object, root.ValueKind);
+
+        foreach (var name in new[] { "nodes_generated", "nodes_expanded", "duplicates_skipped", "frozen_deadlocks", "max_frontier", "max_depth", "time_ms", "peak_memory_bytes", }) {
+            Assert.Equal(JsonValueKind.Number, root.GetProperty(name).ValueKind);
+        }
+
+        Assert.Equal(JsonValueKind.False, root.GetProperty("depth_pruned").ValueKind);
+        Assert.Equal("", root.GetProperty("exceeded_budget").GetString());
+        Assert.Equal(10, root.EnumerateObject().Count());
+
+        Assert.Equal(statistics.TotalNodes, root.GetProperty("nodes_generated").GetInt32());
+        Assert.Equal(statistics.NodesExpanded, root.GetProperty("nodes_expanded").GetInt32());
+        Assert.True(root.GetProperty("nodes_expanded").GetInt32() > 0);
+    }
+}
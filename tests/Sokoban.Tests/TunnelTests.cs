@@ -0,0 +1,41 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class TunnelTests {
+
+    // The left box has to be parked on the first cell of the tunnel so the player can walk up and around
+    // to push the right box onto its target. Pushing it through the tunnel would block the way on the target.
+    private const string PARKED_BOX_LEVEL =
+        "##########\n" +
+        "###    ###\n" +
+        "### ## ###\n" +
+        "#@$   .$.#\n" +
+        "##########";
+
+    [Fact]
+    public void DetectsTunnelsAlongThePushAxis() {
+        Level level = new Level(PARKED_BOX_LEVEL);
+        int right = level.Direction('r');
+        int down = level.Direction('d');
+
+        Assert.True(level.IsTunnel(level.Cell(3, 4), right));
+        Assert.True(level.IsTunnel(level.Cell(3, 5), right));
+        Assert.False(level.IsTunnel(level.Cell(3, 3), right));
+        Assert.False(level.IsTunnel(level.Cell(3, 4), down));
+        Assert.True(level.IsTunnel(level.Cell(2, 3), down));
+    }
+
+    [Fact]
+    public void SolvesLevelThatParksBoxInTunnel() {
+        Param param = new Param(new[] { PARKED_BOX_LEVEL, "BFS", "100", "--tunnels", });
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        SearchResult result = Program.Search(level, state, param);
+
+        Assert.Equal(SearchOutcome.Solved, result.Outcome);
+
+        string moves = string.Concat(result.SolutionPath!.Skip(1).Select(node => node.Action));
+        Assert.True(new Verifier(level, state, moves).Solved);
+    }
+}
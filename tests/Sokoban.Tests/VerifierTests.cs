@@ -0,0 +1,45 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class VerifierTests {
+
+    private const string LEVEL =
+        "#####\n" +
+        "#@$.#\n" +
+        "#####";
+
+    [Fact]
+    public void DecodesRunCountsBeforeReplaying() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        Verifier verifier = Verifier.Decode(level, state, "1R");
+
+        Assert.True(verifier.Solved);
+        Assert.Equal(1, verifier.PushesCount);
+    }
+
+    [Theory]
+    [InlineData("3")]
+    [InlineData("r0")]
+    [InlineData("2(R")]
+    public void ReportsMalformedRunCountsAsIllegal(string moves) {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        Verifier verifier = Verifier.Decode(level, state, moves);
+
+        Assert.False(verifier.Legal);
+        Assert.False(verifier.Solved);
+        Assert.NotEqual("", verifier.Malformed);
+    }
+
+    [Fact]
+    public void ReportsNonLurdCharactersAsIllegal() {
+        (Level level, State state) = Program.CreateLevel(LEVEL);
+
+        Verifier verifier = Verifier.Decode(level, state, "x");
+
+        Assert.False(verifier.Legal);
+        Assert.Equal(0, verifier.IllegalMove);
+    }
+}
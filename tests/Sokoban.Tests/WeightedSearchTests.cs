@@ -0,0 +1,41 @@
+using Xunit;
+
+namespace Sokoban.Tests;
+
+public class WeightedSearchTests {
+
+    // Shortest solution: 11 moves
+    private const string LEVEL =
+        "#######\n" +
+        "#@    #\n" +
+        "# $ $ #\n" +
+        "#.   .#\n" +
+        "#######";
+
+    // Returns the result of a search with the given arguments after the depth
+    private static SearchResult Solve(string strategy, params string[] options) {
+        Param param = new Param(new[] { LEVEL, strategy, "50", }.Concat(options).ToArray());
+        (Level level, State state) = Program.CreateLevel(param.Level);
+
+        return Program.Search(level, state, param);
+    }
+
+    [Fact]
+    public void WeightOneMatchesAStar() {
+        SearchResult aStar = Solve("A*");
+        SearchResult weighted = Solve("WA*", "--weight", "1");
+
+        Assert.Equal(SearchOutcome.Solved, weighted.Outcome);
+        Assert.Equal(aStar.SolutionPath!.Count, weighted.SolutionPath!.Count);
+        Assert.Equal(aStar.Statistics.NodesExpanded, weighted.Statistics.NodesExpanded);
+        Assert.Equal(12, weighted.SolutionPath!.Count);
+    }
+
+    [Fact]
+    public void HigherWeightStillSolves() {
+        SearchResult weighted = Solve("WA*", "--weight", "3");
+
+        Assert.Equal(SearchOutcome.Solved, weighted.Outcome);
+        Assert.True(weighted.SolutionPath!.Count >= 12);
+    }
+}